@@ -0,0 +1,37 @@
+// Big integer price arithmetic replaced by `u256`
+//
+// Only built with `biguint-price` feature, so tests can compare cycles of both implementations on
+// the same transaction. It has the same interface as `u256::Product`.
+
+use core::cmp::Ordering;
+
+use num_bigint::BigUint;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Product(BigUint);
+
+impl Product {
+    pub fn new<T: Into<BigUint>>(value: T) -> Self {
+        Product(value.into())
+    }
+
+    pub fn mul<T: Into<BigUint>>(self, rhs: T) -> Self {
+        Product(self.0 * rhs.into())
+    }
+
+    pub fn mul_pow10(self, exp: u32) -> Self {
+        Product(self.0 * BigUint::from(10u8).pow(exp))
+    }
+}
+
+impl Ord for Product {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl PartialOrd for Product {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
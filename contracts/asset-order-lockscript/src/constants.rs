@@ -0,0 +1,9 @@
+// Constants shared by order validator and host side price math tests
+
+// The dex fee rate is measured in 1/10000
+pub const FEE_DECIMAL: u128 = 10000;
+
+// Filled amount is allowed to exceed order amount by at most 0.1%, such order is treated as
+// completed. Set OVERFILL_TOLERANCE to 0 to reject any overfilled order.
+pub const OVERFILL_TOLERANCE: u128 = 1;
+pub const OVERFILL_TOLERANCE_DECIMAL: u128 = 1000;
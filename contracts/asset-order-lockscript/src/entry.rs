@@ -1,31 +1,103 @@
 // Asset order lock script
 //
-// An Asset order lock script using 43 bytes cell data
+// An Asset order lock script using 43 bytes(version 1), at least 45 bytes(version 2) or at least
+// 52 bytes(version 3) cell data
 //
-// This asset order lock script has three scenarios:
+// This asset order lock script has four scenarios:
 //
 // 1. The placing order operation will generate cells, which contain sudt type script and
 // data conforming to certain rules.
 //
-// Cell data includes six fields:
+// Cell data includes following fields, its molecule schema is owned by `order-data` crate:
 // - sudt amount: uint128
 // - version: uint8
 // - order amount: uint128
 // - price effect: uint64
-// - price exponent: int8
-// - order type: uint8
+// - price exponent: int8, in range [-32, 32]. Price must be normalized, price effect can't have
+//   trailing zeros unless exponent is 32
+// - order type: uint8, 0 sell ckb, 1 buy ckb, 2 sell sudt for quote sudt(version 2 and 3 only)
+// - fee rate: uint16, measured in 1/10000, version 2 and 3 only. Version 1 order fee rate is fixed
+//   at 0.3%
+// - quote sudt type hash: byte32, sell sudt order only
+// - expiry: uint64, optional, version 2 and 3 only. Absolute since in block number or timestamp
+// - referral: optional, version 2 and 3 only. Referrer lock hash(byte32) followed by referrer share
+//   of order fee(uint16, measured in 1/10000)
+//
+// Version 3 order replaces price effect and exponent with a rational price:
+// - price numerator: uint64
+// - price denominator: uint64, the fraction must be reduced
 //
 // 2. When the prices and quantities of different buy and sell orders match, they will be
 // matched into a transaction to complete the purchase needs of both buyers and sellers.
 // At the same time, the cell data fields of inputs and outputs will be updated accordingly.
+// Order output is at the same index as order input, unless matcher puts output index(uint32) in
+// order input witness output type. An output can't be claimed by two orders.
+// Dex fee paid by orders must be sent to the fee collector, whose lock hash is stored in the
+// fee config cell dep. Dex fee and referral fee of all matched orders in the transaction are
+// checked together by the lock group of the first matched order, so are assets received by each
+// user lock, which orders with and without delegate share.
+// Matcher can mark maker order by appending order role(uint8, 0 taker, 1 maker) to output index.
+// Maker order pays maker fee rate in fee config cell dep if it's lower than order fee rate. A
+// negative maker fee rate is a rebate paid in the asset maker receives, deducted from dex fee.
+// Matched orders with makers must have at least one taker, and rebate of each asset can't exceed
+// dex fee of that asset minus referral fee.
+// Referrer share of fee paid by referred order is sent to referrer lock instead of fee collector.
+// Quote sudt got by partial filled sell sudt order is sent to user lock in another output.
+// Completed sell sudt order can refund its leftover sudt to user lock in another output, matcher
+// appends its refund output index(uint32) after order role. Order with remained order amount can
+// only complete when the leftover can't buy even one unit of quote sudt.
+// Order expiry isn't checked when matching, nothing on chain proves that the transaction is
+// committed before expiry. An expired order can still be matched until it's refunded.
 //
 // 3. Order cancellation
 //
-// There are two ways to cancel an order:
+// There are three ways to cancel an order:
 // - Provide witness args and pass built-in supported lock verification. Currently only pw-lock is
-//   supported.
+//   supported. User lock script is put in witness lock field, followed by its signature payload.
+//   Order without witness lock flag(high bit of version byte) can also put user lock script in
+//   witness input type instead. Dynamically loaded user lock reads witness lock by itself, it can't
+//   verify a payload following the script, so its script must be in witness input type. Orders with
+//   witness lock flag owned by such locks are cancelled by the second way. Default
+//   secp256k1_blake160_sighash_all lock is verified natively through secp256k1 library cell dep, no
+//   dynamic lock cell dep is required. When its script is in witness lock, signature payload signs
+//   the transaction with only signature zeroed in witness lock. secp256k1_blake160_multisig_all
+//   lock is also verified natively, its multisig script and signatures are put in the same place as
+//   secp256k1 signature. If multisig lock args contain since, every order input must set a since
+//   not earlier than it. Failed dynamically loaded user lock exits with its own error code,
+//   negative exit codes are reserved for it, see `ExitError` for the encoding.
 // - Provide another input cell, it's lock hash is equal to order's lock args. And that input's
 //   witness args must not be empty to be compatible with anyone can pay lock.
+// - After expiry, anyone can unlock orders by setting input since to order expiry, as long as all
+//   assets are refunded to user lock.
+//
+// Order flagged strict cancel(second high bit of version byte) must be refunded to user lock when
+// cancelled by owner, at most 1 ckb tx fee can be paid from its capacity.
+//
+// Order lock args are user lock hash(byte32), optionally followed by delegate lock hash(byte32).
+// Delegate can cancel orders by the first two ways, in place of user lock, but all assets must be
+// refunded to user lock.
+//
+// 4. Order update
+//
+// Once authorized by the same ways as cancellation, owner can update orders without cancelling.
+// Update operation(uint8) is put in witness output type of the first order in current lock group,
+// followed by output indexes(uint32) of updated orders. Without it, owner transaction is a plain
+// cancellation, order outputs in it are new orders.
+// - Merge(1) all orders in current lock group into one order cell, at the given output index. The
+//   merged order must keep price, order type and other order parameters, and its capacity, sudt
+//   amount and order amount are the exact sums.
+// - Re-price(2) a single order, at the given output index. Its price and order amount can be
+//   changed, while capacity, sudt amount and other order parameters are kept. Matching never allows
+//   price change.
+// - Transfer(3) orders to another user lock, followed by one output index per order. Each order in
+//   current lock group is moved to the output at the same position, locked by order lock with new
+//   args. Capacity, type and data are kept. Orders of different owners can be transferred in one
+//   transaction.
+
+#[cfg(not(feature = "simulator"))]
+use alloc::vec::Vec;
+#[cfg(feature = "simulator")]
+use std::vec::Vec;
 
 use core::convert::TryFrom;
 use core::result::Result;
@@ -33,64 +105,202 @@ use core::result::Result;
 use ckb_dyn_lock::locks::{
     CODE_HASH_SECP256K1_KECCAK256_SIGHASH_ALL, CODE_HASH_SECP256K1_KECCAK256_SIGHASH_ALL_DUAL,
 };
+use ckb_lib_secp256k1::CODE_HASH_SECP256K1;
 use ckb_std::ckb_constants::{CellField, Source};
-use ckb_std::ckb_types::packed::{Byte, Script, ScriptReader, WitnessArgs};
+use ckb_std::ckb_types::packed::{Byte, Byte32, BytesOpt, Script, ScriptReader, WitnessArgs};
 use ckb_std::ckb_types::{bytes::Bytes, prelude::*};
 use ckb_std::dynamic_loading::CKBDLContext;
 use ckb_std::error::SysError;
-use ckb_std::high_level::{load_cell_lock_hash, load_script, load_witness_args, QueryIter};
+use ckb_std::high_level::{
+    load_cell_lock_hash, load_input, load_input_since, load_script, load_tx_hash,
+    load_witness_args, QueryIter,
+};
 use ckb_std::syscalls;
 use dynamic_loading::DynLock;
-use share::hash::blake2b_256;
+use share::hash::{blake2b_256, new_blake2b, Blake2b};
+
+use crate::error::{Error, ExitError};
+
+const MOLECULE_HEADER_LEN: usize = 4;
+const LOCK_HASH_LEN: usize = 32;
 
-use crate::error::Error;
+// Default lock script of ckb, referenced by type hash in genesis or by its code hash
+const SECP256K1_BLAKE160_SIGHASH_ALL_TYPE_HASH: [u8; 32] = [
+    155, 215, 224, 111, 62, 207, 75, 224, 242, 252, 210, 24, 139, 35, 241, 185, 252, 200, 142, 93,
+    75, 101, 168, 99, 123, 23, 114, 59, 189, 163, 204, 232,
+];
+const SECP256K1_BLAKE160_SIGHASH_ALL_DATA_HASH: [u8; 32] = [
+    112, 159, 63, 218, 18, 245, 97, 207, 172, 249, 34, 115, 197, 122, 152, 254, 222, 24, 138, 63,
+    26, 89, 177, 248, 136, 209, 19, 249, 204, 224, 134, 73,
+];
+// Multisig lock script of ckb, only referenced by type hash in genesis
+const SECP256K1_BLAKE160_MULTISIG_ALL_TYPE_HASH: [u8; 32] = [
+    92, 80, 105, 235, 8, 87, 239, 198, 94, 27, 202, 12, 7, 223, 52, 195, 22, 99, 179, 98, 47, 211,
+    135, 108, 135, 99, 32, 252, 150, 52, 226, 168,
+];
+const BLAKE160_LEN: usize = 20;
+const SIGNATURE_LEN: usize = 65;
+const SINCE_LEN: usize = 8;
+// Multisig script header: reserved(must be 0), require first n, threshold, pubkeys count
+const MULTISIG_HEADER_LEN: usize = 4;
 
-pub fn main() -> Result<(), Error> {
+const SINCE_FLAGS_MASK: u64 = 0xff00_0000_0000_0000;
+const SINCE_VALUE_MASK: u64 = 0x00ff_ffff_ffff_ffff;
+const SINCE_METRIC_EPOCH: u64 = 0x2000_0000_0000_0000;
+
+pub fn main() -> Result<(), ExitError> {
     let script = load_script()?;
-    let user_lock_hash: Bytes = script.args().unpack();
+    let args: Bytes = script.args().unpack();
 
-    // The length of user lock hash must be 32 bytes
-    if user_lock_hash.len() != 32 {
-        return Err(Error::WrongUserLockHashSize);
+    // The length of user lock hash must be 32 bytes, optionally followed by 32 bytes delegate lock
+    // hash
+    if args.len() != LOCK_HASH_LEN && args.len() != LOCK_HASH_LEN * 2 {
+        return Err(Error::WrongUserLockHashSize.into());
     }
+    let user_lock_hash = args.slice(..LOCK_HASH_LEN);
+    let delegate_lock_hash = args.slice(LOCK_HASH_LEN..);
 
     // Check cancellation
-    // Firstly, we check whether there's a witness to cancel directly
+    // Firstly, we check whether there's a witness to cancel directly. Witness with only output
+    // type is used by matcher to locate order output.
     if let Ok(witness_args) = load_witness_args(0, Source::GroupInput) {
-        return validate_witness(witness_args, user_lock_hash);
+        if !is_output_index_witness(&witness_args) {
+            let authority = validate_witness(witness_args, &user_lock_hash, &delegate_lock_hash)?;
+            let result = match authority {
+                Authority::Owner => crate::order_validator::validate_owner_update(&user_lock_hash),
+                Authority::Delegate => {
+                    crate::order_validator::validate_delegate_cancellation(&user_lock_hash)
+                }
+            };
+            return result.map_err(ExitError::from);
+        }
     }
 
     // Secondly, check whether there is an input's lock hash equal to this order lock args(user
-    // lock hash). If it exists, verify it according to the process of cancellation.
-    // if it does not exist, verify it according to the process of matching transaction.
+    // lock hash or delegate lock hash). If it exists, verify it according to the process of
+    // cancellation. if it does not exist, verify it according to the process of matching
+    // transaction.
     let input_position = QueryIter::new(load_cell_lock_hash, Source::Input)
         .position(|lock_hash| lock_hash == &user_lock_hash[..]);
+    let delegate_position =
+        QueryIter::new(load_cell_lock_hash, Source::Input).position(|lock_hash| {
+            !delegate_lock_hash.is_empty() && lock_hash == &delegate_lock_hash[..]
+        });
 
-    match input_position {
-        None => return crate::order_validator::validate(),
+    let result = match (input_position, delegate_position) {
+        // Thirdly, check whether there is an expired order, it must be refunded to user.
+        (None, None) if crate::order_validator::has_expired_order()? => {
+            crate::order_validator::validate_expired_refund(&user_lock_hash)
+        }
+        (None, None) => crate::order_validator::validate(),
         // Since anyone can pay lock dones't require signature to unlock, we must make
         // sure that witness args isn't empty.
-        Some(position) if load_witness_args(position, Source::Input).is_ok() => Ok(()),
+        (Some(position), _) if load_witness_args(position, Source::Input).is_ok() => {
+            crate::order_validator::validate_owner_update(&user_lock_hash)
+        }
+        (None, Some(position)) if load_witness_args(position, Source::Input).is_ok() => {
+            crate::order_validator::validate_delegate_cancellation(&user_lock_hash)
+        }
         _ => Err(Error::CancelOrderWithoutWitness),
-    }
+    };
+
+    result.map_err(ExitError::from)
 }
 
-fn validate_witness(witness_args: WitnessArgs, user_lock_hash: Bytes) -> Result<(), Error> {
-    // TODO: move user_lock_bytes into lock field
-    let user_lock_bytes: Bytes = {
-        let opt_bytes = witness_args.input_type();
-        let user_lock = opt_bytes.to_opt().ok_or_else(|| Error::UserLockNotFound)?;
-        user_lock.unpack()
+// Delegate can only cancel orders, assets are still refunded to owner
+enum Authority {
+    Owner,
+    Delegate,
+}
+
+pub fn is_output_index_witness(witness_args: &WitnessArgs) -> bool {
+    witness_args.lock().is_none()
+        && witness_args.input_type().is_none()
+        && witness_args.output_type().is_some()
+}
+
+// User lock script in witness input type collides with type scripts reading it, it's only kept for
+// orders without witness lock flag. Returns user lock script and signature payload in witness lock.
+fn load_user_lock_bytes(witness_args: &WitnessArgs) -> Result<(Bytes, Option<Bytes>), Error> {
+    if let Some(user_lock) = witness_args.input_type().to_opt() {
+        if crate::order_validator::has_witness_lock_order()? {
+            return Err(Error::InputTypeUserLockDisabled);
+        }
+
+        return Ok((user_lock.unpack(), None));
+    }
+
+    // Witness lock starts with user lock script, its molecule header is the total size
+    let lock: Bytes = {
+        let opt_bytes = witness_args.lock();
+        let lock = opt_bytes.to_opt().ok_or_else(|| Error::UserLockNotFound)?;
+        lock.unpack()
     };
+    if lock.len() < MOLECULE_HEADER_LEN {
+        return Err(Error::UserLockScriptEncoding);
+    }
+
+    let mut buf = [0u8; MOLECULE_HEADER_LEN];
+    buf.copy_from_slice(&lock[..MOLECULE_HEADER_LEN]);
+    let user_lock_len = u32::from_le_bytes(buf) as usize;
+    if user_lock_len > lock.len() {
+        return Err(Error::UserLockScriptEncoding);
+    }
+
+    Ok((
+        lock.slice(..user_lock_len),
+        Some(lock.slice(user_lock_len..)),
+    ))
+}
+
+// Lock script in witness is either user lock or delegate lock, it's verified in the same way
+fn validate_witness(
+    witness_args: WitnessArgs,
+    user_lock_hash: &[u8],
+    delegate_lock_hash: &[u8],
+) -> Result<Authority, ExitError> {
+    let (user_lock_bytes, signature) = load_user_lock_bytes(&witness_args)?;
     ScriptReader::verify(&user_lock_bytes[..], false).map_err(|_| Error::UserLockScriptEncoding)?;
 
     let user_lock = Script::new_unchecked(user_lock_bytes);
-    if &blake2b_256(user_lock.as_slice())[..] != &user_lock_hash[..] {
-        return Err(Error::UserLockHashNotMatch);
-    }
+    let lock_hash = blake2b_256(user_lock.as_slice());
+    let authority = if &lock_hash[..] == user_lock_hash {
+        Authority::Owner
+    } else if !delegate_lock_hash.is_empty() && &lock_hash[..] == delegate_lock_hash {
+        Authority::Delegate
+    } else {
+        return Err(Error::UserLockHashNotMatch.into());
+    };
+
+    validate_user_lock(witness_args, user_lock, signature)?;
+    Ok(authority)
+}
+
+fn validate_user_lock(
+    witness_args: WitnessArgs,
+    user_lock: Script,
+    signature: Option<Bytes>,
+) -> Result<(), ExitError> {
+    let user_lock_bytes = user_lock.as_bytes();
 
     let hash_type = HashType::try_from(user_lock.hash_type())?;
     let code_hash = user_lock.code_hash();
+    if is_secp256k1_blake160_sighash_all(&code_hash, &hash_type) {
+        let lock_args: Bytes = user_lock.args().unpack();
+        validate_secp256k1_blake160(&witness_args, &user_lock_bytes, &lock_args, signature)?;
+        return Ok(());
+    }
+    if is_secp256k1_blake160_multisig_all(&code_hash, &hash_type) {
+        let lock_args: Bytes = user_lock.args().unpack();
+        validate_secp256k1_multisig(&witness_args, &user_lock_bytes, &lock_args, signature)?;
+        return Ok(());
+    }
+
+    // Dynamically loaded lock verifies its own witness lock, not the payload after user lock
+    if signature.is_some() {
+        return Err(Error::DynamicLockInWitnessLock.into());
+    }
+
     let data_hash = match find_cell_dep(code_hash.unpack(), hash_type)? {
         Some(data_hash) => data_hash,
         // FIXME: Our forked pw-lock to verify signature, only personal hash is supported
@@ -98,10 +308,10 @@ fn validate_witness(witness_args: WitnessArgs, user_lock_hash: Bytes) -> Result<
             let alternative = CODE_HASH_SECP256K1_KECCAK256_SIGHASH_ALL_DUAL;
             match find_cell_dep(alternative, HashType::Data)? {
                 Some(data_hash) => data_hash,
-                None => return Err(Error::UserLockCellDepNotFound),
+                None => return Err(Error::UserLockCellDepNotFound.into()),
             }
         }
-        _ => return Err(Error::UserLockCellDepNotFound),
+        _ => return Err(Error::UserLockCellDepNotFound.into()),
     };
 
     let mut ctx = unsafe { CKBDLContext::<[u8; 128 * 1024]>::new() };
@@ -113,10 +323,292 @@ fn validate_witness(witness_args: WitnessArgs, user_lock_hash: Bytes) -> Result<
     Ok(())
 }
 
+fn is_secp256k1_blake160_sighash_all(code_hash: &Byte32, hash_type: &HashType) -> bool {
+    let code_hash: [u8; 32] = code_hash.unpack();
+    match hash_type {
+        HashType::Type => code_hash == SECP256K1_BLAKE160_SIGHASH_ALL_TYPE_HASH,
+        HashType::Data | HashType::Data1 | HashType::Data2 => {
+            code_hash == SECP256K1_BLAKE160_SIGHASH_ALL_DATA_HASH
+        }
+    }
+}
+
+fn validate_secp256k1_blake160(
+    witness_args: &WitnessArgs,
+    user_lock_bytes: &Bytes,
+    lock_args: &Bytes,
+    signature: Option<Bytes>,
+) -> Result<(), Error> {
+    if lock_args.len() != BLAKE160_LEN {
+        return Err(Error::WrongSecp256k1LockArgsSize);
+    }
+
+    // Secp256k1 library is loaded by code hash, make sure it exists
+    if find_cell_dep(CODE_HASH_SECP256K1, HashType::Data)?.is_none() {
+        return Err(Error::UserLockCellDepNotFound);
+    }
+
+    let verified = match signature {
+        // User lock script is in witness input type, witness lock is the signature
+        None => share::signature::validate_blake160_sighash_all(lock_args),
+        Some(signature) => {
+            if signature.len() != SIGNATURE_LEN {
+                return Err(Error::WrongSecp256k1SignatureSize);
+            }
+
+            let message = sighash_all(witness_args, user_lock_bytes, SIGNATURE_LEN)?;
+            share::signature::validate_blake160_signature(lock_args, &signature, &message)
+        }
+    };
+
+    verified.map_err(|_| Error::Secp256k1SignatureNotMatch)
+}
+
+fn is_secp256k1_blake160_multisig_all(code_hash: &Byte32, hash_type: &HashType) -> bool {
+    let code_hash: [u8; 32] = code_hash.unpack();
+    *hash_type == HashType::Type && code_hash == SECP256K1_BLAKE160_MULTISIG_ALL_TYPE_HASH
+}
+
+// Multisig lock args are blake160 of multisig script, optionally followed by since(uint64).
+// Multisig payload is multisig script followed by signatures of threshold count.
+fn validate_secp256k1_multisig(
+    witness_args: &WitnessArgs,
+    user_lock_bytes: &Bytes,
+    lock_args: &Bytes,
+    payload: Option<Bytes>,
+) -> Result<(), Error> {
+    if lock_args.len() != BLAKE160_LEN && lock_args.len() != BLAKE160_LEN + SINCE_LEN {
+        return Err(Error::WrongMultisigLockArgsSize);
+    }
+
+    if find_cell_dep(CODE_HASH_SECP256K1, HashType::Data)?.is_none() {
+        return Err(Error::UserLockCellDepNotFound);
+    }
+
+    let (lock_prefix, payload) = match payload {
+        Some(payload) => (user_lock_bytes.to_vec(), payload),
+        // User lock script is in witness input type, witness lock is the multisig payload
+        None => {
+            let opt_bytes = witness_args.lock();
+            let lock = opt_bytes.to_opt().ok_or_else(|| Error::UserLockNotFound)?;
+            (Vec::new(), lock.unpack())
+        }
+    };
+
+    let multisig = Multisig::parse(&payload)?;
+    if &blake2b_256(multisig.script)[..BLAKE160_LEN] != &lock_args[..BLAKE160_LEN] {
+        return Err(Error::MultisigScriptHashNotMatch);
+    }
+
+    if lock_args.len() > BLAKE160_LEN {
+        let mut buf = [0u8; SINCE_LEN];
+        buf.copy_from_slice(&lock_args[BLAKE160_LEN..]);
+        validate_multisig_since(u64::from_le_bytes(buf))?;
+    }
+
+    let message = {
+        let mut lock_prefix = lock_prefix;
+        lock_prefix.extend_from_slice(multisig.script);
+        sighash_all(witness_args, &lock_prefix, multisig.signatures.len())?
+    };
+
+    let mut signed_pubkey_hashes = Vec::new();
+    signed_pubkey_hashes.resize(multisig.threshold, [0u8; BLAKE160_LEN]);
+    share::signature::recover_blake160_pubkey_hashes(
+        multisig.signatures,
+        &message,
+        &mut signed_pubkey_hashes,
+    )
+    .map_err(|_| Error::MultisigSignatureNotMatch)?;
+
+    // Each pubkey can only sign once, and first n pubkeys are required to sign
+    let mut used = Vec::new();
+    used.resize(multisig.pubkeys_count(), false);
+    for signed_pubkey_hash in signed_pubkey_hashes.iter() {
+        let position = multisig
+            .pubkey_hashes
+            .chunks(BLAKE160_LEN)
+            .enumerate()
+            .position(|(idx, pubkey_hash)| !used[idx] && pubkey_hash == &signed_pubkey_hash[..]);
+
+        match position {
+            Some(idx) => used[idx] = true,
+            None => return Err(Error::MultisigSignatureNotMatch),
+        }
+    }
+    if used.iter().take(multisig.require_first_n).any(|used| !used) {
+        return Err(Error::MultisigSignatureNotMatch);
+    }
+
+    Ok(())
+}
+
+struct Multisig<'a> {
+    require_first_n: usize,
+    threshold:       usize,
+    // Multisig script, includes header and pubkey hashes
+    script:          &'a [u8],
+    pubkey_hashes:   &'a [u8],
+    signatures:      &'a [u8],
+}
+
+impl<'a> Multisig<'a> {
+    fn parse(payload: &'a [u8]) -> Result<Self, Error> {
+        if payload.len() < MULTISIG_HEADER_LEN {
+            return Err(Error::MultisigScriptEncoding);
+        }
+
+        let reserved = payload[0];
+        let require_first_n = payload[1] as usize;
+        let threshold = payload[2] as usize;
+        let pubkeys_count = payload[3] as usize;
+        if reserved != 0
+            || pubkeys_count == 0
+            || threshold == 0
+            || threshold > pubkeys_count
+            || require_first_n > threshold
+        {
+            return Err(Error::MultisigScriptEncoding);
+        }
+
+        let script_len = MULTISIG_HEADER_LEN + pubkeys_count * BLAKE160_LEN;
+        if payload.len() != script_len + threshold * SIGNATURE_LEN {
+            return Err(Error::MultisigScriptEncoding);
+        }
+
+        Ok(Multisig {
+            require_first_n,
+            threshold,
+            script: &payload[..script_len],
+            pubkey_hashes: &payload[MULTISIG_HEADER_LEN..script_len],
+            signatures: &payload[script_len..],
+        })
+    }
+
+    fn pubkeys_count(&self) -> usize {
+        self.pubkey_hashes.len() / BLAKE160_LEN
+    }
+}
+
+// Same as multisig lock, every input since in current group must use the same flags as lock since,
+// and not be earlier than it.
+fn validate_multisig_since(lock_since: u64) -> Result<(), Error> {
+    for since in QueryIter::new(load_input_since, Source::GroupInput) {
+        if since & SINCE_FLAGS_MASK != lock_since & SINCE_FLAGS_MASK {
+            return Err(Error::MultisigSinceNotReached);
+        }
+
+        let reached = if lock_since & SINCE_FLAGS_MASK & SINCE_METRIC_EPOCH != 0 {
+            epoch_reached(since & SINCE_VALUE_MASK, lock_since & SINCE_VALUE_MASK)
+        } else {
+            since & SINCE_VALUE_MASK >= lock_since & SINCE_VALUE_MASK
+        };
+        if !reached {
+            return Err(Error::MultisigSinceNotReached);
+        }
+    }
+
+    Ok(())
+}
+
+// Epoch is packed as number(24 bits), index(16 bits) and length(16 bits), compare them as
+// fractional epochs.
+fn epoch_reached(epoch: u64, lock_epoch: u64) -> bool {
+    let unpack = |epoch: u64| {
+        let number = epoch & 0xff_ffff;
+        let index = (epoch >> 24) & 0xffff;
+        let length = ((epoch >> 40) & 0xffff).max(1);
+        (number, index, length)
+    };
+
+    let (number, index, length) = unpack(epoch);
+    let (lock_number, lock_index, lock_length) = unpack(lock_epoch);
+    if number != lock_number {
+        return number > lock_number;
+    }
+
+    index as u128 * lock_length as u128 >= lock_index as u128 * length as u128
+}
+
+// Same digest as secp256k1_blake160_sighash_all lock, except that lock prefix in the first witness
+// lock is kept, only its signature payload is zeroed. Lock prefix is user lock script if it's in
+// witness lock, and also multisig script for multisig lock.
+fn sighash_all(
+    witness_args: &WitnessArgs,
+    lock_prefix: &[u8],
+    signature_len: usize,
+) -> Result<[u8; 32], Error> {
+    let zeroed_lock = {
+        let mut lock = lock_prefix.to_vec();
+        lock.resize(lock_prefix.len() + signature_len, 0);
+        Bytes::from(lock)
+    };
+    let first_witness = witness_args
+        .clone()
+        .as_builder()
+        .lock(
+            BytesOpt::new_builder()
+                .set(Some(zeroed_lock.pack()))
+                .build(),
+        )
+        .build();
+
+    let mut blake2b = new_blake2b();
+    blake2b.update(&load_tx_hash()?);
+    digest_witness(&mut blake2b, first_witness.as_slice());
+
+    // Rest witnesses in current group
+    for index in 1.. {
+        match load_witness(index, Source::GroupInput) {
+            Ok(witness) => digest_witness(&mut blake2b, &witness),
+            Err(SysError::IndexOutOfBound) => break,
+            Err(err) => return Err(err.into()),
+        }
+    }
+
+    // Witnesses without input
+    let inputs_len = QueryIter::new(load_input, Source::Input).count();
+    for index in inputs_len.. {
+        match load_witness(index, Source::Input) {
+            Ok(witness) => digest_witness(&mut blake2b, &witness),
+            Err(SysError::IndexOutOfBound) => break,
+            Err(err) => return Err(err.into()),
+        }
+    }
+
+    let mut message = [0u8; 32];
+    blake2b.finalize(&mut message);
+    Ok(message)
+}
+
+fn digest_witness(blake2b: &mut Blake2b, witness: &[u8]) {
+    blake2b.update(&(witness.len() as u64).to_le_bytes());
+    blake2b.update(witness);
+}
+
+fn load_witness(index: usize, source: Source) -> Result<Vec<u8>, SysError> {
+    let mut buf = [0u8; 1024];
+    match syscalls::load_witness(&mut buf, 0, index, source) {
+        Ok(len) => Ok(buf[..len].to_vec()),
+        Err(SysError::LengthNotEnough(actual_len)) => {
+            let mut witness = Vec::new();
+            witness.resize(actual_len, 0);
+            syscalls::load_witness(&mut witness, 0, index, source)?;
+            Ok(witness)
+        }
+        Err(err) => Err(err),
+    }
+}
+
+// Data1 and Data2 reference code by data hash as Data does, they only select newer vm versions.
+// Dynamically loaded user lock always runs in the same vm as this order lock, so its vm version
+// follows order lock's hash type instead of user lock's.
 #[derive(Debug, PartialEq, Eq)]
 enum HashType {
     Type,
     Data,
+    Data1,
+    Data2,
 }
 
 impl TryFrom<Byte> for HashType {
@@ -127,6 +619,8 @@ impl TryFrom<Byte> for HashType {
         match type_num {
             0 => Ok(HashType::Data),
             1 => Ok(HashType::Type),
+            2 => Ok(HashType::Data1),
+            4 => Ok(HashType::Data2),
             _ => Err(Error::UnknownUserLockHashType),
         }
     }
@@ -134,9 +628,11 @@ impl TryFrom<Byte> for HashType {
 
 type DataHash = [u8; 32];
 
+// Cells in dep group are already expanded in cell dep source, while dep group cell itself isn't
+// included. So user lock shipped by dep group is found as well.
 fn find_cell_dep(hash: [u8; 32], hash_type: HashType) -> Result<Option<DataHash>, Error> {
     let cell_field = match hash_type {
-        HashType::Data => CellField::DataHash,
+        HashType::Data | HashType::Data1 | HashType::Data2 => CellField::DataHash,
         HashType::Type => CellField::TypeHash,
     };
 
@@ -158,7 +654,7 @@ fn find_cell_dep(hash: [u8; 32], hash_type: HashType) -> Result<Option<DataHash>
                 syscalls::load_cell_by_field(&mut buf, 0, i, Source::CellDep, CellField::DataHash)?;
                 Ok(Some(buf))
             }
-            HashType::Data => Ok(Some(hash)),
+            HashType::Data | HashType::Data1 | HashType::Data2 => Ok(Some(hash)),
         };
     }
 
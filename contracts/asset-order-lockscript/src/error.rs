@@ -46,6 +46,126 @@ pub enum Error {
     DynamicLoadingMemoryNotEnough,
     DynamicLoadingCellNotFound = 35,
     DynamicLoadingInvalidAlign,
+
+    // Order deal
+    OutputOrderAmountMismatch = 40,
+    OrderOverfilled,
+    OrderFeeRateTooHigh,
+    OutputOrderFeeRateChanged,
+    FeeConfigCellDepNotFound,
+    WrongFeeConfigDataSize = 45,
+    DexFeeNotPaid,
+
+    // Order expiry
+    UnsupportedOrderExpiry = 50,
+    OrderNotExpired = 52,
+    OutputOrderExpiryChanged,
+    ExpiredOrderNotRefunded,
+
+    // Sudt pair
+    QuoteSudtNotReceived = 55,
+    OutputNotAQuoteSudtCell,
+    UnexpectedRefundOutput,
+    WrongRefundOutput,
+
+    // Output mapping
+    WrongOutputIndexWitness = 60,
+    DuplicatedOrderOutput,
+
+    // Order merge and re-pricing
+    RepricedMultipleOrders = 65,
+    MergedOrderAmountMismatch,
+    RepricedOrderAssetsChanged,
+
+    // Order price
+    PriceExponentOutOfRange = 70,
+    PriceNotNormalized,
+    PriceDenominatorIsZero,
+
+    // Maker and taker
+    UnknownOrderRole = 75,
+    MakerFeeRateOutOfRange,
+    MakerWithoutTaker,
+    MakerRebateExceedsFee,
+
+    // Referral
+    ReferralShareOutOfRange = 80,
+    OutputOrderReferralChanged,
+    ReferralFeeNotPaid,
+
+    // Witness lock
+    InputTypeUserLockDisabled = 85,
+    OutputOrderWitnessLockChanged,
+    DynamicLockInWitnessLock,
+
+    // Built-in secp256k1 blake160 lock
+    WrongSecp256k1LockArgsSize = 90,
+    WrongSecp256k1SignatureSize,
+    Secp256k1SignatureNotMatch,
+
+    // Built-in secp256k1 blake160 multisig lock
+    WrongMultisigLockArgsSize = 95,
+    MultisigScriptEncoding,
+    MultisigScriptHashNotMatch,
+    MultisigSignatureNotMatch,
+    MultisigSinceNotReached,
+
+    // Unexpected errors
+    UnknownSysError = 100,
+
+    // Delegate
+    DelegateCancellationNotRefunded = 105,
+
+    // Strict cancellation
+    StrictCancellationNotRefunded = 110,
+    OutputOrderStrictCancelChanged,
+
+    // Ownership transfer
+    TransferredOrderCountMismatch = 116,
+    WrongTransferredUserLockHashSize,
+    TransferredOrderChanged,
+
+    // Owner update operation
+    WrongOwnerUpdateWitness = 125,
+    UpdatedOrderLockChanged,
+}
+
+/// Exit error, either order lock error or dynamically loaded user lock's own error code
+pub enum ExitError {
+    OrderLock(Error),
+    UserLock(i32),
+}
+
+// Negative exit codes are reserved for user lock:
+// - User lock codes from -64 to -1 are kept as they are.
+// - User lock codes from 1 to 63 are mapped to -65 to -127, that's -64 - code.
+// - Other user lock codes are reported as -128.
+const USER_LOCK_KEPT_CODE_MIN: i32 = -64;
+const USER_LOCK_MAPPED_CODE_MAX: i32 = 63;
+
+impl ExitError {
+    pub fn code(self) -> i8 {
+        match self {
+            ExitError::OrderLock(err) => err as i8,
+            ExitError::UserLock(code) if code >= USER_LOCK_KEPT_CODE_MIN && code < 0 => code as i8,
+            ExitError::UserLock(code) if code > 0 && code <= USER_LOCK_MAPPED_CODE_MAX => {
+                (USER_LOCK_KEPT_CODE_MIN - code) as i8
+            }
+            ExitError::UserLock(_) => i8::MIN,
+        }
+    }
+}
+
+impl From<Error> for ExitError {
+    fn from(err: Error) -> Self {
+        ExitError::OrderLock(err)
+    }
+}
+
+impl From<SysError> for ExitError {
+    fn from(err: SysError) -> Self {
+        ExitError::OrderLock(err.into())
+    }
 }
 
 impl From<SysError> for Error {
@@ -56,7 +176,7 @@ impl From<SysError> for Error {
             ItemMissing => Self::ItemMissing,
             LengthNotEnough(_) => Self::LengthNotEnough,
             Encoding => Self::Encoding,
-            Unknown(err_code) => panic!("unexpected sys error {}", err_code),
+            Unknown(_) => Self::UnknownSysError,
         }
     }
 }
@@ -76,16 +196,26 @@ impl From<ckb_std::dynamic_loading::Error> for Error {
     }
 }
 
-impl From<dynamic_loading::Error> for Error {
+impl From<dynamic_loading::Error> for ExitError {
     fn from(err: dynamic_loading::Error) -> Self {
         use dynamic_loading::Error as LError;
 
         match err {
-            LError::DynamicLoading(e) => e.into(),
-            LError::ValidationFunctionNotFound => Error::ValidationFunctionNotFound,
-            LError::ValidateFailure(err_code) => {
-                panic!("user lock validation failure {}", err_code)
+            LError::DynamicLoading(e) => ExitError::OrderLock(e.into()),
+            LError::ValidationFunctionNotFound => {
+                ExitError::OrderLock(Error::ValidationFunctionNotFound)
             }
+            LError::ValidateFailure(err_code) => ExitError::UserLock(err_code),
+        }
+    }
+}
+
+impl From<order_data::Error> for Error {
+    fn from(err: order_data::Error) -> Self {
+        match err {
+            order_data::Error::WrongDataSize => Error::WrongOrderDataSize,
+            order_data::Error::UnexpectedVersion => Error::UnexpectedOrderVersion,
+            order_data::Error::VersionFieldsMismatch => Error::WrongOrderDataSize,
         }
     }
 }
@@ -10,9 +10,14 @@
 #![feature(alloc_error_handler)]
 #![feature(panic_info_message)]
 
+#[cfg(feature = "biguint-price")]
+mod biguint;
+mod constants;
 mod entry;
 mod error;
 mod order_validator;
+#[cfg(not(feature = "biguint-price"))]
+mod u256;
 
 ckb_std::entry!(program_entry);
 
@@ -24,6 +29,6 @@ fn program_entry() -> i8 {
     // Call main function and return error code
     match entry::main() {
         Ok(_) => 0,
-        Err(err) => err as i8,
+        Err(err) => err.code(),
     }
 }
@@ -4,46 +4,533 @@ use alloc::vec::Vec;
 use std::vec::Vec;
 
 use core::convert::TryFrom;
+use core::iter;
 use core::result::Result;
 
 use ckb_std::ckb_types::{bytes::Bytes, packed::Script};
 use ckb_std::error::SysError;
 use ckb_std::high_level::{
     load_cell_capacity, load_cell_data, load_cell_lock, load_cell_lock_hash, load_cell_type_hash,
-    load_input, QueryIter,
+    load_input, load_input_since, load_script, load_witness_args, QueryIter,
 };
 use ckb_std::{ckb_constants::Source, ckb_types::prelude::*};
-use num_bigint::BigUint;
+use order_data::{OrderData, Price as RawPrice, Referral};
 
+#[cfg(feature = "biguint-price")]
+use crate::biguint::Product;
+use crate::constants::{FEE_DECIMAL, OVERFILL_TOLERANCE, OVERFILL_TOLERANCE_DECIMAL};
 use crate::error::Error;
+#[cfg(not(feature = "biguint-price"))]
+use crate::u256::Product;
+
+// Version 1 order fee rate is fixed at 0.3%
+const VERSION_1_FEE_RATE: u16 = 30;
+
+// Capacity reserved for a sudt cell to receive bought tokens, 8 bytes capacity, 65 bytes lock
+// script with 32 bytes args, 65 bytes sudt type script and 16 bytes amount.
+const SUDT_CELL_CAPACITY: u64 = 154_00_000_000;
+
+// The cell data length of version 1 order is 43 bytes, version 2 appends 2 bytes fee rate, 32
+// bytes quote sudt type hash for sell sudt order, optional 8 bytes expiry and optional 34 bytes
+// referral
+
+// Type hash of the fee config cell dep, which is created with type id args
+// 0x029031e1f659fe0c3ac4434cf4b14c9c5352d1bdda54c17b8286b879c0935399. Its data is the 32 bytes
+// lock hash of dex fee collector.
+const FEE_CONFIG_TYPE_HASH: [u8; 32] = [
+    153, 21, 30, 95, 96, 212, 127, 15, 215, 102, 204, 181, 112, 80, 139, 33, 48, 29, 25, 97, 51,
+    38, 164, 229, 231, 114, 235, 235, 78, 57, 71, 7,
+];
+const FEE_COLLECTOR_LOCK_HASH_LEN: usize = 32;
+const MAKER_FEE_RATE_LEN: usize = 2;
+
+// Referrer share is measured in 1/10000 of order fee
+const REFERRAL_SHARE_DECIMAL: u128 = 10000;
+
+// Order lock args are user lock hash, optionally followed by delegate lock hash
+const USER_LOCK_HASH_LEN: usize = 32;
+
+// Strict cancellation can pay at most 1 ckb tx fee from refunded capacity
+const MAX_CANCELLATION_TX_FEE: u128 = 100_000_000;
+
+// Order expiry is an absolute since value, only block number and timestamp metrics are supported.
+const SINCE_FLAGS_MASK: u64 = 0xff00_0000_0000_0000;
+const SINCE_VALUE_MASK: u64 = 0x00ff_ffff_ffff_ffff;
+const SINCE_METRIC_BLOCK_NUMBER: u64 = 0x0000_0000_0000_0000;
+const SINCE_METRIC_TIMESTAMP: u64 = 0x4000_0000_0000_0000;
+
+// Matcher can put order output index in witness output type, it's a molecule Uint32, optionally
+// followed by order role byte. Completed sell sudt order appends refund output index(Uint32) after
+// order role to refund its leftover sudt.
+const OUTPUT_INDEX_LEN: usize = 4;
+const ORDER_ROLE_LEN: usize = 1;
+const REFUND_INDEX_LEN: usize = 4;
+
+// Owner puts update operation in witness output type of the first order in current group, followed
+// by output indexes(uint32) of updated orders. Without it, owner transaction is a cancellation.
+const OWNER_UPDATE_MERGE: u8 = 1;
+const OWNER_UPDATE_REPRICE: u8 = 2;
+const OWNER_UPDATE_TRANSFER: u8 = 3;
+
+// Price is effect * 10^exponent, exponent must be in this range. Price is normalized, effect has
+// no trailing zeros unless exponent reaches the max, so every price has exactly one encoding.
+const PRICE_EXPONENT_MIN: i8 = -32;
+const PRICE_EXPONENT_MAX: i8 = 32;
 
-// The dex fee rate is fixed at 0.3%
-const FEE: u128 = 3;
-const FEE_DECIMAL: u128 = 1000;
+pub fn validate() -> Result<(), Error> {
+    let group_lock_hash = load_cell_lock_hash(0, Source::GroupInput)?;
+    let lock_hashes = QueryIter::new(load_cell_lock_hash, Source::Input).collect::<Vec<_>>();
+
+    // Dex fee of all matched orders in the transaction is checked once, by the lock group of the
+    // first matched order, so it can't be split among groups. Only that group loads all matched
+    // orders, other groups only check their own orders, which are matched as dispatched by entry.
+    let first_matched_orders = load_matched_orders(&lock_hashes, true)?;
+    let checks_dex_fee = matches!(
+        first_matched_orders.first(),
+        Some((_, lock_hash)) if lock_hash == &group_lock_hash
+    );
+    let matched_orders = if checks_dex_fee {
+        load_matched_orders(&lock_hashes, false)?
+    } else {
+        lock_hashes
+            .iter()
+            .enumerate()
+            .filter(|(_, lock_hash)| *lock_hash == &group_lock_hash)
+            .map(|(index, lock_hash)| (index, *lock_hash))
+            .collect()
+    };
 
-// The cell data length of order book is fixed at 41 bytes
-const ORDER_DATA_LEN: usize = 43;
-const PRICE_BYTES_LEN: usize = 9;
-const VERSION: u8 = 1;
+    // Find the position of the order book input in the entire inputs to find its output, and
+    // then verify the order data of the input and output. An output, including refund output,
+    // can't be claimed by two orders.
+    let mut order_cells = Vec::new();
+    let mut claimed_outputs = Vec::new();
+    for (input_index, _) in matched_orders {
+        let (output_index, role, refund_index) = load_order_output(input_index)?;
+        for index in iter::once(output_index).chain(refund_index) {
+            if claimed_outputs.contains(&index) {
+                return Err(Error::DuplicatedOrderOutput);
+            }
+            claimed_outputs.push(index);
+        }
 
-pub fn validate() -> Result<(), Error> {
-    // Find inputs in current group
-    let orders = QueryIter::new(load_input, Source::GroupInput).collect::<Vec<_>>();
-    // Find all inputs in the current transaction
-    let inputs = QueryIter::new(load_input, Source::Input).collect::<Vec<_>>();
-
-    // Find the position of the order book input in the entire inputs to find the output
-    // corresponding to the position, and then verify the order data of the input and output
-    for index in 0..inputs.len() {
-        let input = inputs.get(index).unwrap().as_slice();
-        if orders.iter().any(|order| order.as_slice() == input) {
-            match validate_order_cells(index) {
-                Ok(_) => continue,
-                Err(err) => return Err(err),
+        order_cells.push((input_index, output_index, refund_index, role));
+    }
+
+    // Maker fee rate is configured in fee config cell, only load it when there's a maker order
+    let has_maker = order_cells
+        .iter()
+        .any(|(_, _, _, role)| role == &OrderRole::Maker);
+    let maker_fee_rate = if has_maker {
+        load_fee_config()?.maker_fee_rate
+    } else {
+        None
+    };
+
+    // Maker rebate is paid out of taker fee, so matched orders can't be all makers
+    let has_taker = order_cells
+        .iter()
+        .any(|(_, _, _, role)| role == &OrderRole::Taker);
+    if checks_dex_fee && has_maker && !has_taker {
+        return Err(Error::MakerWithoutTaker);
+    }
+
+    // Orders of the same user lock can be in different lock groups, such as delegate orders, so
+    // assets received by users are also checked once, grouped by user lock hash
+    let mut dex_fee = DexFee::default();
+    let mut user_received = Vec::new();
+    for (input_index, output_index, refund_index, role) in order_cells {
+        let fee_rate = match role {
+            OrderRole::Maker => maker_fee_rate,
+            OrderRole::Taker => None,
+        };
+        let deal = validate_order_cells(input_index, output_index, refund_index, fee_rate)?;
+        dex_fee.add(deal.fee, deal.referral);
+        add_user_received(&mut user_received, deal.user_lock_hash, deal.user_received);
+    }
+
+    if checks_dex_fee {
+        validate_user_received(&user_received)?;
+        validate_dex_fee(dex_fee)?;
+    }
+
+    Ok(())
+}
+
+// Inputs locked by order lock of every lock group that is matched in the transaction, along with
+// their lock hashes. Each lock group is checked once, by its first input. With `first_group_only`,
+// later lock groups aren't checked once a matched group is found.
+fn load_matched_orders(
+    lock_hashes: &[[u8; 32]],
+    first_group_only: bool,
+) -> Result<Vec<(usize, [u8; 32])>, Error> {
+    let script = load_script()?;
+
+    let mut groups: Vec<([u8; 32], bool)> = Vec::new();
+    let mut matched = Vec::new();
+    for (index, lock_hash) in lock_hashes.iter().enumerate() {
+        let is_matched = match groups.iter().find(|(hash, _)| hash == lock_hash) {
+            Some((_, is_matched)) => *is_matched,
+            None if first_group_only && !matched.is_empty() => continue,
+            // First input of a lock group
+            None => {
+                let lock = load_cell_lock(index, Source::Input)?;
+                let is_matched =
+                    is_order_lock(&lock, &script) && is_matched_group(index, &lock, lock_hashes)?;
+                groups.push((*lock_hash, is_matched));
+                is_matched
+            }
+        };
+        if is_matched {
+            matched.push((index, *lock_hash));
+        }
+    }
+
+    Ok(matched)
+}
+
+// Same dispatch as entry, a lock group is matched unless it's authorized by its owner or delegate,
+// through witness of its first input or an input cell, or it has expired orders to refund.
+fn is_matched_group(
+    first_index: usize,
+    order_lock: &Script,
+    lock_hashes: &[[u8; 32]],
+) -> Result<bool, Error> {
+    if let Ok(witness_args) = load_witness_args(first_index, Source::Input) {
+        if !crate::entry::is_output_index_witness(&witness_args) {
+            return Ok(false);
+        }
+    }
+
+    let args: Bytes = order_lock.args().unpack();
+    if args.len() != USER_LOCK_HASH_LEN && args.len() != USER_LOCK_HASH_LEN * 2 {
+        return Err(Error::WrongUserLockHashSize);
+    }
+    let (user_lock_hash, delegate_lock_hash) = args.split_at(USER_LOCK_HASH_LEN);
+    let authorized = lock_hashes.iter().any(|lock_hash| {
+        &lock_hash[..] == user_lock_hash
+            || (!delegate_lock_hash.is_empty() && &lock_hash[..] == delegate_lock_hash)
+    });
+    if authorized {
+        return Ok(false);
+    }
+
+    let group_lock_hash = lock_hashes[first_index];
+    for (index, lock_hash) in lock_hashes.iter().enumerate() {
+        if lock_hash != &group_lock_hash {
+            continue;
+        }
+
+        let order = Order::try_from(load_cell_data(index, Source::Input)?.as_slice())?;
+        if let Some(expiry) = order.expiry {
+            if expiry.reached_by_since(load_input_since(index, Source::Input)?) {
+                return Ok(false);
             }
         }
     }
 
+    Ok(true)
+}
+
+// Order lock with any args, referenced by the same code hash and hash type as current script
+fn is_order_lock(lock: &Script, script: &Script) -> bool {
+    lock.code_hash().as_slice() == script.code_hash().as_slice()
+        && lock.hash_type().as_slice() == script.hash_type().as_slice()
+}
+
+// Witness lock flagged orders can't be cancelled by user lock script in witness input type. Only
+// version byte is checked, so orders with broken data are still cancellable.
+pub fn has_witness_lock_order() -> Result<bool, Error> {
+    let flagged = QueryIter::new(load_cell_data, Source::GroupInput)
+        .any(|data| order_data::witness_lock_flag(&data));
+
+    Ok(flagged)
+}
+
+// An order is expired when its input since reaches order expiry. Since the transaction can't be
+// committed before its inputs' since, we don't need to check header deps here.
+pub fn has_expired_order() -> Result<bool, Error> {
+    let orders = QueryIter::new(load_cell_data, Source::GroupInput).collect::<Vec<_>>();
+
+    for (index, data) in orders.iter().enumerate() {
+        let order = Order::try_from(data.as_slice())?;
+        if let Some(expiry) = order.expiry {
+            if expiry.reached_by_since(load_input_since(index, Source::GroupInput)?) {
+                return Ok(true);
+            }
+        }
+    }
+
+    Ok(false)
+}
+
+// Anyone can unlock expired orders, as long as all orders in current group are expired and
+// their capacity and sudt are refunded to the user lock.
+pub fn validate_expired_refund(user_lock_hash: &[u8]) -> Result<(), Error> {
+    let mut refund = Assets::default();
+    let orders = QueryIter::new(load_input, Source::GroupInput).count();
+
+    for index in 0..orders {
+        let input = Cell::load(index, Source::GroupInput)?;
+        let order = input.to_order()?;
+
+        let since = load_input_since(index, Source::GroupInput)?;
+        match order.expiry {
+            Some(expiry) if expiry.reached_by_since(since) => (),
+            _ => return Err(Error::OrderNotExpired),
+        }
+
+        refund.add_ckb(u128::from(input.capacity));
+        if let Some(type_hash) = input.type_hash()? {
+            refund.add_sudt(type_hash, order.sudt_amount);
+        }
+    }
+
+    let mut lock_hash = [0u8; 32];
+    lock_hash.copy_from_slice(user_lock_hash);
+    if !received_at_least(&lock_hash, &refund)? {
+        return Err(Error::ExpiredOrderNotRefunded);
+    }
+
+    Ok(())
+}
+
+// Delegate can cancel orders in current group, but never redirect funds. All their capacity and
+// sudt must be refunded to the user lock.
+pub fn validate_delegate_cancellation(user_lock_hash: &[u8]) -> Result<(), Error> {
+    let refund = group_order_assets()?;
+
+    let mut lock_hash = [0u8; 32];
+    lock_hash.copy_from_slice(user_lock_hash);
+    if !received_at_least(&lock_hash, &refund)? {
+        return Err(Error::DelegateCancellationNotRefunded);
+    }
+
+    Ok(())
+}
+
+// Strict cancel flagged orders must be refunded to the user lock when cancelled, only a bounded tx
+// fee can be paid from refunded capacity. All orders in current group are refunded together.
+fn validate_strict_cancellation(user_lock_hash: &[u8]) -> Result<(), Error> {
+    let strict = QueryIter::new(load_cell_data, Source::GroupInput)
+        .any(|data| order_data::strict_cancel_flag(&data));
+    if !strict {
+        return Ok(());
+    }
+
+    let mut refund = group_order_assets()?;
+    refund.ckb = refund.ckb.saturating_sub(MAX_CANCELLATION_TX_FEE);
+
+    let mut lock_hash = [0u8; 32];
+    lock_hash.copy_from_slice(user_lock_hash);
+    if !received_at_least(&lock_hash, &refund)? {
+        return Err(Error::StrictCancellationNotRefunded);
+    }
+
+    Ok(())
+}
+
+// Each order in current group is moved to the output at the same position in transfer operation,
+// only user lock hash(and delegate lock hash) in order lock args can be changed. Order data and
+// assets are kept, so it's neither a match nor a cancellation.
+fn validate_ownership_transfer(output_indexes: &[usize]) -> Result<(), Error> {
+    let orders = QueryIter::new(load_input, Source::GroupInput).count();
+    if output_indexes.len() != orders {
+        return Err(Error::TransferredOrderCountMismatch);
+    }
+
+    // An output can't be shared by transferred orders
+    for (position, output_index) in output_indexes.iter().enumerate() {
+        if output_indexes[..position].contains(output_index) {
+            return Err(Error::WrongOwnerUpdateWitness);
+        }
+    }
+
+    let script = load_script()?;
+    for (index, output_index) in output_indexes.iter().enumerate() {
+        let input = Cell::load(index, Source::GroupInput)?;
+        let output = Cell::load(*output_index, Source::Output)?;
+
+        if !is_order_lock(&output.lock_script, &script) {
+            return Err(Error::TransferredOrderChanged);
+        }
+
+        let args: Bytes = output.lock_script.args().unpack();
+        if args.len() != USER_LOCK_HASH_LEN && args.len() != USER_LOCK_HASH_LEN * 2 {
+            return Err(Error::WrongTransferredUserLockHashSize);
+        }
+
+        if output.capacity != input.capacity
+            || output.data != input.data
+            || output.type_hash()? != input.type_hash()?
+        {
+            return Err(Error::TransferredOrderChanged);
+        }
+    }
+
+    Ok(())
+}
+
+// Capacity and sudt of all orders in current group
+fn group_order_assets() -> Result<Assets, Error> {
+    let mut assets = Assets::default();
+    let orders = QueryIter::new(load_input, Source::GroupInput).count();
+
+    for index in 0..orders {
+        let input = Cell::load(index, Source::GroupInput)?;
+        let order = input.to_order()?;
+
+        assets.add_ckb(u128::from(input.capacity));
+        if let Some(type_hash) = input.type_hash()? {
+            assets.add_sudt(type_hash, order.sudt_amount);
+        }
+    }
+
+    Ok(assets)
+}
+
+// Owner update operation and output indexes of updated orders
+enum OwnerUpdate {
+    Cancel,
+    Merge(usize),
+    Reprice(usize),
+    Transfer(Vec<usize>),
+}
+
+fn load_owner_update() -> Result<OwnerUpdate, Error> {
+    let witness: Bytes = match load_witness_args(0, Source::GroupInput) {
+        Ok(witness_args) => match witness_args.output_type().to_opt() {
+            Some(witness) => witness.unpack(),
+            None => return Ok(OwnerUpdate::Cancel),
+        },
+        Err(_) => return Ok(OwnerUpdate::Cancel),
+    };
+
+    let (operation, output_indexes) = witness
+        .split_first()
+        .ok_or(Error::WrongOwnerUpdateWitness)?;
+    if output_indexes.len() % OUTPUT_INDEX_LEN != 0 {
+        return Err(Error::WrongOwnerUpdateWitness);
+    }
+    let output_indexes = output_indexes
+        .chunks(OUTPUT_INDEX_LEN)
+        .map(|index| {
+            let mut buf = [0u8; OUTPUT_INDEX_LEN];
+            buf.copy_from_slice(index);
+            u32::from_le_bytes(buf) as usize
+        })
+        .collect::<Vec<_>>();
+
+    match (*operation, output_indexes.as_slice()) {
+        (OWNER_UPDATE_MERGE, [output_index]) => Ok(OwnerUpdate::Merge(*output_index)),
+        (OWNER_UPDATE_REPRICE, [output_index]) => Ok(OwnerUpdate::Reprice(*output_index)),
+        (OWNER_UPDATE_TRANSFER, output_indexes) if !output_indexes.is_empty() => {
+            Ok(OwnerUpdate::Transfer(output_indexes.to_vec()))
+        }
+        _ => Err(Error::WrongOwnerUpdateWitness),
+    }
+}
+
+// Once authorized, owner can update orders in current group by operation in witness. Without it,
+// it's a plain cancellation, which is checked by strict mode, and outputs locked by order lock are
+// just new orders. Several orders can be merged into one order cell with the same price, while a
+// single order can be re-priced only by re-price operation. In both cases, assets are conserved.
+// Transferred orders are moved to outputs with new order lock args as they are.
+pub fn validate_owner_update(user_lock_hash: &[u8]) -> Result<(), Error> {
+    let order_lock_hash = load_cell_lock_hash(0, Source::GroupInput)?;
+
+    let (output_index, repriced) = match load_owner_update()? {
+        OwnerUpdate::Cancel => return validate_strict_cancellation(user_lock_hash),
+        OwnerUpdate::Merge(output_index) => (output_index, false),
+        OwnerUpdate::Reprice(output_index) => (output_index, true),
+        OwnerUpdate::Transfer(output_indexes) => {
+            return validate_ownership_transfer(&output_indexes);
+        }
+    };
+    let merged = Cell::load(output_index, Source::Output)?;
+    if merged.lock_hash != order_lock_hash {
+        return Err(Error::UpdatedOrderLockChanged);
+    }
+    let merged_order = merged.to_order()?;
+
+    let mut capacity = 0u128;
+    let mut sudt_amount = 0u128;
+    let mut order_amount = 0u128;
+    let orders = QueryIter::new(load_input, Source::GroupInput).count();
+    if repriced && orders != 1 {
+        return Err(Error::RepricedMultipleOrders);
+    }
+    for index in 0..orders {
+        let input = Cell::load(index, Source::GroupInput)?;
+        let order = input.to_order()?;
+
+        if input.type_hash()? != merged.type_hash()? {
+            return Err(Error::OutputTypeHashChanged);
+        }
+
+        if input.data.len() != merged.data.len() || order.version != merged_order.version {
+            return Err(Error::OutputOrderDataSizeChanged);
+        }
+
+        if !repriced && order.price != merged_order.price {
+            return Err(Error::OutputOrderPriceChanged);
+        }
+
+        if order.type_ != merged_order.type_
+            || order.quote_type_hash != merged_order.quote_type_hash
+        {
+            return Err(Error::OutputOrderTypeChanged);
+        }
+
+        if order.fee_rate != merged_order.fee_rate {
+            return Err(Error::OutputOrderFeeRateChanged);
+        }
+
+        if order.expiry != merged_order.expiry {
+            return Err(Error::OutputOrderExpiryChanged);
+        }
+
+        if order.referral != merged_order.referral {
+            return Err(Error::OutputOrderReferralChanged);
+        }
+
+        if order.witness_lock != merged_order.witness_lock {
+            return Err(Error::OutputOrderWitnessLockChanged);
+        }
+
+        if order.strict_cancel != merged_order.strict_cancel {
+            return Err(Error::OutputOrderStrictCancelChanged);
+        }
+
+        capacity += u128::from(input.capacity);
+        sudt_amount = sudt_amount
+            .checked_add(order.sudt_amount)
+            .ok_or(Error::MergedOrderAmountMismatch)?;
+        order_amount = order_amount
+            .checked_add(order.order_amount)
+            .ok_or(Error::MergedOrderAmountMismatch)?;
+    }
+
+    if repriced {
+        if u128::from(merged.capacity) != capacity || merged_order.sudt_amount != sudt_amount {
+            return Err(Error::RepricedOrderAssetsChanged);
+        }
+
+        // Order amount can be changed along with price
+        if merged_order.order_amount == 0 {
+            return Err(Error::OrderAmountIsZero);
+        }
+
+        return Ok(());
+    }
+
+    if u128::from(merged.capacity) != capacity
+        || merged_order.sudt_amount != sudt_amount
+        || merged_order.order_amount != order_amount
+    {
+        return Err(Error::MergedOrderAmountMismatch);
+    }
+
     Ok(())
 }
 
@@ -52,24 +539,80 @@ enum OrderState {
     PartialFilled,
     SellCKBCompleted,
     BuyCKBCompleted,
+    SellSudtCompleted,
 }
 
-fn validate_order_cells(index: usize) -> Result<(), Error> {
-    let input = Cell::load(index, Source::Input)?;
-    let output = Cell::load(index, Source::Output)?;
+// Dex fee paid by an order and assets its user must receive
+struct OrderDeal {
+    fee:            OrderFee,
+    referral:       Option<Referral>,
+    user_lock_hash: [u8; 32],
+    user_received:  Assets,
+}
+
+// Order output is located by output index in witness output type. If it isn't provided, the
+// output at the same index as order input is used. Matcher can append order role to output index
+// to mark a maker order, order is taker by default. Refund output index is only provided by
+// completed sell sudt order with leftover sudt.
+fn load_order_output(input_index: usize) -> Result<(usize, OrderRole, Option<usize>), Error> {
+    let witness_args = match load_witness_args(input_index, Source::Input) {
+        Ok(witness_args) => witness_args,
+        Err(_) => return Ok((input_index, OrderRole::Taker, None)),
+    };
+
+    let output_witness: Bytes = match witness_args.output_type().to_opt() {
+        Some(output_witness) => output_witness.unpack(),
+        None => return Ok((input_index, OrderRole::Taker, None)),
+    };
+    let (role, refund_index) = match output_witness.len() {
+        OUTPUT_INDEX_LEN => (OrderRole::Taker, None),
+        len if len == OUTPUT_INDEX_LEN + ORDER_ROLE_LEN => {
+            (OrderRole::try_from(output_witness[OUTPUT_INDEX_LEN])?, None)
+        }
+        len if len == OUTPUT_INDEX_LEN + ORDER_ROLE_LEN + REFUND_INDEX_LEN => {
+            let role = OrderRole::try_from(output_witness[OUTPUT_INDEX_LEN])?;
+            let mut buf = [0u8; REFUND_INDEX_LEN];
+            buf.copy_from_slice(&output_witness[OUTPUT_INDEX_LEN + ORDER_ROLE_LEN..]);
+            (role, Some(u32::from_le_bytes(buf) as usize))
+        }
+        _ => return Err(Error::WrongOutputIndexWitness),
+    };
+
+    let mut buf = [0u8; OUTPUT_INDEX_LEN];
+    buf.copy_from_slice(&output_witness[..OUTPUT_INDEX_LEN]);
+    Ok((u32::from_le_bytes(buf) as usize, role, refund_index))
+}
+
+// Maker order pays maker fee rate if it's lower than order fee rate
+fn validate_order_cells(
+    input_index: usize,
+    output_index: usize,
+    refund_index: Option<usize>,
+    maker_fee_rate: Option<i16>,
+) -> Result<OrderDeal, Error> {
+    let input = Cell::load(input_index, Source::Input)?;
+    let output = Cell::load(output_index, Source::Output)?;
 
     let input_order = input.to_order()?;
     if input_order.order_amount == 0 {
         return Err(Error::OrderAmountIsZero);
     }
 
-    let user_lock_hash: Bytes = input.lock_script.args().unpack();
+    let fee_rate = match maker_fee_rate {
+        Some(maker_fee_rate) => i32::from(input_order.fee_rate).min(i32::from(maker_fee_rate)),
+        None => i32::from(input_order.fee_rate),
+    };
+
+    // Delegate order args append delegate lock hash after user lock hash
+    let args: Bytes = input.lock_script.args().unpack();
+    let user_lock_hash = &args[..USER_LOCK_HASH_LEN];
     let order_state = if output.lock_hash == input.lock_hash {
         OrderState::PartialFilled
-    } else if output.lock_hash == &user_lock_hash[..] {
+    } else if &output.lock_hash[..] == user_lock_hash {
         match input_order.type_ {
             OrderType::SellCKB => OrderState::SellCKBCompleted,
             OrderType::BuyCKB => OrderState::BuyCKBCompleted,
+            OrderType::SellSudt => OrderState::SellSudtCompleted,
         }
     } else {
         return Err(Error::UnknownOutputLock);
@@ -93,6 +636,26 @@ fn validate_order_cells(index: usize) -> Result<(), Error> {
             return Err(Error::OutputOrderTypeChanged);
         }
 
+        if output_order.fee_rate != input_order.fee_rate {
+            return Err(Error::OutputOrderFeeRateChanged);
+        }
+
+        if output_order.expiry != input_order.expiry {
+            return Err(Error::OutputOrderExpiryChanged);
+        }
+
+        if output_order.referral != input_order.referral {
+            return Err(Error::OutputOrderReferralChanged);
+        }
+
+        if output_order.witness_lock != input_order.witness_lock {
+            return Err(Error::OutputOrderWitnessLockChanged);
+        }
+
+        if output_order.strict_cancel != input_order.strict_cancel {
+            return Err(Error::OutputOrderStrictCancelChanged);
+        }
+
         if output_order.order_amount == 0 {
             return Err(Error::OrderAmountIsZero);
         }
@@ -136,24 +699,127 @@ fn validate_order_cells(index: usize) -> Result<(), Error> {
         }
     }
 
-    match input_order.type_ {
-        OrderType::SellCKB => {
-            validate_sell_ckb_price(&input, &output, order_state == OrderState::SellCKBCompleted)
+    if order_state == OrderState::SellSudtCompleted {
+        if output.type_hash()? != input_order.quote_type_hash {
+            return Err(Error::OutputNotAQuoteSudtCell);
         }
-        OrderType::BuyCKB => {
-            validate_buy_ckb_price(&input, &output, order_state == OrderState::BuyCKBCompleted)
+
+        if output.data.len() < 16 {
+            return Err(Error::OutputNotASudtCell);
         }
     }
+
+    // Leftover sudt of completed sell sudt order is refunded to user lock in refund output
+    let refund = match refund_index {
+        Some(_) if order_state != OrderState::SellSudtCompleted => {
+            return Err(Error::UnexpectedRefundOutput);
+        }
+        Some(refund_index) => {
+            let refund = Cell::load(refund_index, Source::Output)?;
+            if &refund.lock_hash[..] != user_lock_hash
+                || refund.type_hash()? != input.type_hash()?
+                || refund.data.len() < 16
+            {
+                return Err(Error::WrongRefundOutput);
+            }
+            Some(refund)
+        }
+        None => None,
+    };
+
+    let mut user_received = Assets::default();
+    let fee = match input_order.type_ {
+        OrderType::SellCKB => validate_sell_ckb_price(&input, &output, &order_state, fee_rate)?,
+        OrderType::BuyCKB => validate_buy_ckb_price(&input, &output, &order_state, fee_rate)?,
+        OrderType::SellSudt => {
+            let (fee, quote_got) =
+                validate_sell_sudt_price(&input, &output, refund.as_ref(), &order_state, fee_rate)?;
+            if order_state == OrderState::PartialFilled {
+                let quote_type_hash = input_order.quote_type_hash.ok_or(Error::ItemMissing)?;
+                user_received.add_sudt(quote_type_hash, quote_got);
+            }
+            fee
+        }
+    };
+
+    // Completed order output and refund output belong to user, they can't be counted for other
+    // orders
+    if order_state != OrderState::PartialFilled {
+        for cell in iter::once(&output).chain(refund.as_ref()) {
+            user_received.add_ckb(u128::from(cell.capacity));
+            if let Some(type_hash) = cell.type_hash()? {
+                if cell.data.len() >= 16 {
+                    user_received.add_sudt(type_hash, cell.sudt_amount());
+                }
+            }
+        }
+    }
+
+    let mut buf = [0u8; 32];
+    buf.copy_from_slice(user_lock_hash);
+    Ok(OrderDeal {
+        fee,
+        referral: input_order.referral,
+        user_lock_hash: buf,
+        user_received,
+    })
+}
+
+// Require (sold * (1 - fee)) / got <= price
+fn sell_price_matched(order: &Order, fee_rate: i32, sold: u128, got: u128) -> bool {
+    // Require (sold * (1 - fee)) * price_denominator <= got * price_numerator
+    let paid = Product::new(after_fee(fee_rate)).mul(sold);
+    let got = Product::new(FEE_DECIMAL).mul(got);
+    order.price.mul_denominator(paid) <= order.price.mul_numerator(got)
+}
+
+// A completed sell ckb order can only be claimed when its left capacity, after reserving a sudt
+// cell to receive tokens, can't afford even one smallest unit of sudt at order price plus fee.
+fn sell_ckb_claimable(order: &Order, fee_rate: i32, output: &Cell) -> bool {
+    let sellable_ckb = output.capacity.saturating_sub(SUDT_CELL_CAPACITY);
+    sell_claimable(order, fee_rate, u128::from(sellable_ckb))
 }
 
-// For this demo, always allow deal maker to claim tokens for user.
-// FIXME: We should calculate token amount to make sure that deal maker can only claim tokens when
-// this order cannot be matched base on its price anymore. There are some edge cases.
-fn claimable() -> bool {
-    true
+// A completed sell sudt order can only be claimed when its refunded sudt can't afford even one
+// smallest unit of quote sudt at order price plus fee.
+fn sell_claimable(order: &Order, fee_rate: i32, sellable: u128) -> bool {
+    // Require (sellable * (1 - fee)) * price_denominator < price_numerator
+    let sellable = Product::new(after_fee(fee_rate)).mul(sellable);
+    order.price.mul_denominator(sellable) < order.price.mul_numerator(Product::new(FEE_DECIMAL))
 }
 
-fn validate_sell_ckb_price(input: &Cell, output: &Cell, completed: bool) -> Result<(), Error> {
+// A completed buy ckb order can only be claimed when its left sudt, after paying fee, can't buy
+// even one shannon at order price.
+fn buy_ckb_claimable(order: &Order, fee_rate: i32, output_sudt_amount: u128) -> bool {
+    // Require (output_sudt_amount * (1 - fee)) * price_numerator < price_denominator
+    let payable = Product::new(after_fee(fee_rate)).mul(output_sudt_amount);
+    order.price.mul_numerator(payable) < order.price.mul_denominator(Product::new(FEE_DECIMAL))
+}
+
+fn remained_order_amount(order_amount: u128, filled: u128) -> Result<u128, Error> {
+    match order_amount.checked_sub(filled) {
+        Some(remained) => Ok(remained),
+        None => {
+            let overfilled = filled - order_amount;
+
+            // Require overfilled / order_amount <= OVERFILL_TOLERANCE / OVERFILL_TOLERANCE_DECIMAL
+            if Product::new(overfilled).mul(OVERFILL_TOLERANCE_DECIMAL)
+                > Product::new(order_amount).mul(OVERFILL_TOLERANCE)
+            {
+                return Err(Error::OrderOverfilled);
+            }
+
+            Ok(0)
+        }
+    }
+}
+
+fn validate_sell_ckb_price(
+    input: &Cell,
+    output: &Cell,
+    order_state: &OrderState,
+    fee_rate: i32,
+) -> Result<OrderFee, Error> {
     if output.capacity > input.capacity {
         return Err(Error::NegativeCapacityDifference);
     }
@@ -173,35 +839,34 @@ fn validate_sell_ckb_price(input: &Cell, output: &Cell, completed: bool) -> Resu
     let sudt_got = output_sudt_amount - input_sudt_amount;
 
     let order = input.to_order()?;
-    let price_exponent = order.price.biguint_exponent();
-    let price_effect = order.price.biguint_effect();
-    if order.price.is_exponent_negative() {
-        // Require (ckb_sold * 997 / 1000) / sudt_got <= price_effect / price_exponent
-        if BigUint::from(FEE_DECIMAL - FEE) * ckb_sold * price_exponent.clone()
-            > BigUint::from(FEE_DECIMAL) * sudt_got * price_effect.clone()
-        {
-            return Err(Error::PriceMismatch);
-        }
-    } else {
-        let price = price_exponent.clone() * price_effect.clone();
+    let remained = remained_order_amount(order.order_amount, sudt_got)?;
 
-        // Require (ckb_sold * 997 / 1000) / sudt_got <= price_effect * price_exponent
-        if BigUint::from(FEE_DECIMAL - FEE) * ckb_sold
-            > BigUint::from(FEE_DECIMAL) * sudt_got * price
-        {
-            return Err(Error::PriceMismatch);
-        }
+    if !sell_price_matched(&order, fee_rate, u128::from(ckb_sold), sudt_got) {
+        return Err(Error::PriceMismatch);
     }
 
-    let remained = order.order_amount - sudt_got;
-    if completed && remained >= 1 && !claimable() {
+    // Order amount of partial filled order must be decreased by exactly the sudt we got
+    if order_state == &OrderState::PartialFilled && output.to_order()?.order_amount != remained {
+        return Err(Error::OutputOrderAmountMismatch);
+    }
+
+    let completed = order_state == &OrderState::SellCKBCompleted;
+    if completed && remained >= 1 && !sell_ckb_claimable(&order, fee_rate, output) {
         return Err(Error::OrderStillMatchable);
     }
 
-    Ok(())
+    let type_hash = input.type_hash()?.ok_or(Error::ItemMissing)?;
+    let sold = (Asset::Ckb, u128::from(ckb_sold));
+    let received = (Asset::Sudt(type_hash), sudt_got);
+    Ok(OrderFee::new(fee_rate, sold, received))
 }
 
-fn validate_buy_ckb_price(input: &Cell, output: &Cell, completed: bool) -> Result<(), Error> {
+fn validate_buy_ckb_price(
+    input: &Cell,
+    output: &Cell,
+    order_state: &OrderState,
+    fee_rate: i32,
+) -> Result<OrderFee, Error> {
     if input.capacity > output.capacity {
         return Err(Error::NegativeCapacityDifference);
     }
@@ -224,94 +889,550 @@ fn validate_buy_ckb_price(input: &Cell, output: &Cell, completed: bool) -> Resul
     let sudt_paid = input_sudt_amount - output_sudt_amount;
 
     let order = input.to_order()?;
-    let price_exponent = order.price.biguint_exponent();
-    let price_effect = order.price.biguint_effect();
-    if order.price.is_exponent_negative() {
-        // Require ckb_bought / (sudt_paid * 997 / 1000) >= price_effect / price_exponent
-        if BigUint::from(FEE_DECIMAL) * ckb_bought * price_exponent.clone()
-            < BigUint::from(FEE_DECIMAL - FEE) * sudt_paid * price_effect.clone()
-        {
-            return Err(Error::PriceMismatch);
+    let remained = remained_order_amount(order.order_amount, u128::from(ckb_bought))?;
+
+    // Require ckb_bought * price_denominator >= (sudt_paid * (1 - fee)) * price_numerator
+    let bought = Product::new(FEE_DECIMAL).mul(ckb_bought);
+    let paid = Product::new(after_fee(fee_rate)).mul(sudt_paid);
+    if order.price.mul_denominator(bought) < order.price.mul_numerator(paid) {
+        return Err(Error::PriceMismatch);
+    }
+
+    // Order amount of partial filled order must be decreased by exactly the ckb we bought
+    if order_state == &OrderState::PartialFilled && output.to_order()?.order_amount != remained {
+        return Err(Error::OutputOrderAmountMismatch);
+    }
+
+    let completed = order_state == &OrderState::BuyCKBCompleted;
+    if completed && remained >= 1 && !buy_ckb_claimable(&order, fee_rate, output_sudt_amount) {
+        return Err(Error::OrderStillMatchable);
+    }
+
+    let type_hash = input.type_hash()?.ok_or(Error::ItemMissing)?;
+    let sold = (Asset::Sudt(type_hash), sudt_paid);
+    let received = (Asset::Ckb, u128::from(ckb_bought));
+    Ok(OrderFee::new(fee_rate, sold, received))
+}
+
+// Sell sudt order sells sudt in order cell for quote sudt. Its order amount is the quote sudt
+// amount to buy, and price is measured in sold sudt per quote sudt. Since a cell can only hold one
+// type of sudt, quote sudt got by partial filled order is sent to user lock in another output, so
+// is leftover sudt refunded by completed order.
+fn validate_sell_sudt_price(
+    input: &Cell,
+    output: &Cell,
+    refund: Option<&Cell>,
+    order_state: &OrderState,
+    fee_rate: i32,
+) -> Result<(OrderFee, u128), Error> {
+    // Capacity locked in order cell must be kept in partial filled order or returned to user
+    if output.capacity < input.capacity {
+        return Err(Error::NegativeCapacityDifference);
+    }
+
+    let order = input.to_order()?;
+    let input_sudt_amount = input.sudt_amount();
+    let (sudt_sold, quote_got) = match order_state {
+        OrderState::PartialFilled => {
+            let output_sudt_amount = output.sudt_amount();
+            if output_sudt_amount > input_sudt_amount {
+                return Err(Error::NegativeSudtDifference);
+            }
+
+            let output_order_amount = output.to_order()?.order_amount;
+            let quote_got = match order.order_amount.checked_sub(output_order_amount) {
+                Some(quote_got) => quote_got,
+                None => return Err(Error::OutputOrderAmountMismatch),
+            };
+
+            (input_sudt_amount - output_sudt_amount, quote_got)
         }
-    } else {
-        let price = price_exponent.clone() * price_effect.clone();
+        _ => {
+            let refunded = refund.map(|refund| refund.sudt_amount()).unwrap_or(0);
+            if refunded > input_sudt_amount {
+                return Err(Error::NegativeSudtDifference);
+            }
 
-        // Require ckb_bought / (sudt_paid * 997 / 1000) >= price_effect * price_exponent
-        if BigUint::from(FEE_DECIMAL) * ckb_bought
-            < BigUint::from(FEE_DECIMAL - FEE) * price * sudt_paid
-        {
-            return Err(Error::PriceMismatch);
+            (input_sudt_amount - refunded, output.sudt_amount())
         }
+    };
+    let remained = remained_order_amount(order.order_amount, quote_got)?;
+
+    if !sell_price_matched(&order, fee_rate, sudt_sold, quote_got) {
+        return Err(Error::PriceMismatch);
     }
 
-    let remained = order.order_amount - u128::from(ckb_bought);
-    if completed && remained >= 1 && !claimable() {
+    let completed = order_state == &OrderState::SellSudtCompleted;
+    let refunded = input_sudt_amount - sudt_sold;
+    if completed && remained >= 1 && !sell_claimable(&order, fee_rate, refunded) {
         return Err(Error::OrderStillMatchable);
     }
 
-    Ok(())
+    let type_hash = input.type_hash()?.ok_or(Error::ItemMissing)?;
+    let quote_type_hash = order.quote_type_hash.ok_or(Error::ItemMissing)?;
+    let sold = (Asset::Sudt(type_hash), sudt_sold);
+    let received = (Asset::Sudt(quote_type_hash), quote_got);
+    Ok((OrderFee::new(fee_rate, sold, received), quote_got))
 }
 
-#[derive(Debug)]
-struct Price {
-    effect:   u64,
-    exponent: i8,
+// 1 - fee rate, measured in 1/FEE_DECIMAL. Maker rebate makes it greater than FEE_DECIMAL.
+fn after_fee(fee_rate: i32) -> u128 {
+    (FEE_DECIMAL as i32 - fee_rate) as u128
 }
 
-impl TryFrom<[u8; PRICE_BYTES_LEN]> for Price {
+// Referrer gets its share of order fee
+fn referral_fee(fee: u128, share: u16) -> u128 {
+    let share = u128::from(share);
+
+    // Split fee to avoid u128 multiplication overflow
+    fee / REFERRAL_SHARE_DECIMAL * share
+        + fee % REFERRAL_SHARE_DECIMAL * share / REFERRAL_SHARE_DECIMAL
+}
+
+// Price check lets order sell `sold * (1 - fee_rate)` at order price, so fee is `sold * fee_rate`
+fn order_fee(sold: u128, fee_rate: u128) -> u128 {
+    // Split sold to avoid u128 multiplication overflow
+    sold / FEE_DECIMAL * fee_rate + sold % FEE_DECIMAL * fee_rate / FEE_DECIMAL
+}
+
+// Price check makes maker receive `received = value * (1 + rebate_rate)` at order price, so rebate
+// is `received * rebate_rate / (1 + rebate_rate)`
+fn order_rebate(received: u128, rebate_rate: u128) -> u128 {
+    let total_rate = FEE_DECIMAL + rebate_rate;
+
+    // Split received to avoid u128 multiplication overflow
+    received / total_rate * rebate_rate + received % total_rate * rebate_rate / total_rate
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum OrderRole {
+    Taker = 0,
+    Maker = 1,
+}
+
+impl TryFrom<u8> for OrderRole {
     type Error = Error;
 
-    fn try_from(bytes: [u8; PRICE_BYTES_LEN]) -> Result<Price, Self::Error> {
-        let effect = {
-            let mut buf = [0u8; 8];
-            buf.copy_from_slice(&bytes[0..8]);
-            u64::from_le_bytes(buf)
-        };
-        if effect == 0 {
-            return Err(Error::OrderPriceIsZero);
+    fn try_from(byte: u8) -> Result<Self, Self::Error> {
+        match byte {
+            0 => Ok(OrderRole::Taker),
+            1 => Ok(OrderRole::Maker),
+            _ => Err(Error::UnknownOrderRole),
         }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Asset {
+    Ckb,
+    Sudt([u8; 32]),
+}
 
-        let exponent = {
-            let mut buf = [0u8; 1];
-            buf.copy_from_slice(&bytes[8..9]);
-            i8::from_le_bytes(buf)
+// Order pays fee in the asset it sells. Maker order with negative fee rate gets rebate in the
+// asset it receives instead, rebate is paid out of dex fee of the same asset.
+enum OrderFee {
+    Fee(Asset, u128),
+    Rebate(Asset, u128),
+}
+
+impl OrderFee {
+    fn new(fee_rate: i32, sold: (Asset, u128), received: (Asset, u128)) -> Self {
+        if fee_rate >= 0 {
+            OrderFee::Fee(sold.0, order_fee(sold.1, fee_rate as u128))
+        } else {
+            OrderFee::Rebate(received.0, order_rebate(received.1, fee_rate.abs() as u128))
+        }
+    }
+}
+
+// Total ckb and sudt amounts, grouped by sudt type hash
+#[derive(Default, Clone)]
+struct Assets {
+    ckb:  u128,
+    sudt: Vec<([u8; 32], u128)>,
+}
+
+impl Assets {
+    fn merge(&mut self, other: Assets) {
+        self.add_ckb(other.ckb);
+        for (type_hash, amount) in other.sudt {
+            self.add_sudt(type_hash, amount);
+        }
+    }
+
+    fn add(&mut self, asset: Asset, amount: u128) {
+        match asset {
+            Asset::Ckb => self.add_ckb(amount),
+            Asset::Sudt(type_hash) => self.add_sudt(type_hash, amount),
+        }
+    }
+
+    fn add_ckb(&mut self, amount: u128) {
+        self.ckb = self.ckb.saturating_add(amount);
+    }
+
+    fn add_sudt(&mut self, type_hash: [u8; 32], amount: u128) {
+        match self.sudt.iter_mut().find(|(hash, _)| hash == &type_hash) {
+            Some((_, total)) => *total = total.saturating_add(amount),
+            None => self.sudt.push((type_hash, amount)),
+        }
+    }
+
+    fn saturating_sub(&mut self, other: &Assets) {
+        self.ckb = self.ckb.saturating_sub(other.ckb);
+        for (type_hash, amount) in other.sudt.iter() {
+            if let Some((_, total)) = self.sudt.iter_mut().find(|(hash, _)| hash == type_hash) {
+                *total = total.saturating_sub(*amount);
+            }
+        }
+    }
+
+    fn at_least(&self, other: &Assets) -> bool {
+        self.ckb >= other.ckb
+            && other.sudt.iter().all(|(type_hash, amount)| {
+                let total = self.sudt.iter().find(|(hash, _)| hash == type_hash);
+                total.map(|(_, total)| *total).unwrap_or(0) >= *amount
+            })
+    }
+
+    fn is_zero(&self) -> bool {
+        self.ckb == 0 && self.sudt.iter().all(|(_, amount)| *amount == 0)
+    }
+}
+
+#[derive(Default)]
+struct DexFee {
+    fee:      Assets,
+    rebate:   Assets,
+    // Referral fee grouped by referrer lock hash
+    referral: Vec<([u8; 32], Assets)>,
+}
+
+impl DexFee {
+    fn add(&mut self, order_fee: OrderFee, referral: Option<Referral>) {
+        match order_fee {
+            OrderFee::Fee(asset, amount) => {
+                if let Some(referral) = referral {
+                    let fee = referral_fee(amount, referral.share);
+                    self.add_referral(referral.lock_hash, asset, fee);
+                }
+                self.fee.add(asset, amount);
+            }
+            OrderFee::Rebate(asset, amount) => self.rebate.add(asset, amount),
+        }
+    }
+
+    fn add_referral(&mut self, lock_hash: [u8; 32], asset: Asset, amount: u128) {
+        match self
+            .referral
+            .iter_mut()
+            .find(|(hash, _)| hash == &lock_hash)
+        {
+            Some((_, fee)) => fee.add(asset, amount),
+            None => {
+                let mut fee = Assets::default();
+                fee.add(asset, amount);
+                self.referral.push((lock_hash, fee));
+            }
+        }
+    }
+
+    // Rebate is paid out of fee of the same asset, after referral fee
+    fn covers_rebate(&self) -> bool {
+        let mut fee = self.fee.clone();
+        for (_, referral_fee) in self.referral.iter() {
+            fee.saturating_sub(referral_fee);
+        }
+
+        fee.at_least(&self.rebate)
+    }
+
+    // Fee minus rebate and referral fee of each asset
+    fn collector_fee(&self) -> Assets {
+        let mut collector_fee = self.fee.clone();
+        collector_fee.saturating_sub(&self.rebate);
+        for (_, referral_fee) in self.referral.iter() {
+            collector_fee.saturating_sub(referral_fee);
+        }
+
+        collector_fee
+    }
+}
+
+// Fee collector must receive at least the total fee of all matched orders in the transaction, minus
+// rebate and referral fee. Each referrer must receive at least its referral fee.
+fn validate_dex_fee(dex_fee: DexFee) -> Result<(), Error> {
+    if !dex_fee.covers_rebate() {
+        return Err(Error::MakerRebateExceedsFee);
+    }
+
+    let mut collector_fee = dex_fee.collector_fee();
+    let mut referral = dex_fee.referral;
+
+    if !collector_fee.is_zero() {
+        let fee_collector_lock_hash = load_fee_config()?.collector_lock_hash;
+
+        // Referrer can be fee collector itself, then it must receive both fees
+        let referred = referral
+            .iter()
+            .position(|(lock_hash, _)| lock_hash == &fee_collector_lock_hash);
+        if let Some(position) = referred {
+            collector_fee.merge(referral.remove(position).1);
+        }
+
+        if !received_at_least(&fee_collector_lock_hash, &collector_fee)? {
+            return Err(Error::DexFeeNotPaid);
+        }
+    }
+
+    for (lock_hash, referral_fee) in referral.iter() {
+        if !received_at_least(lock_hash, referral_fee)? {
+            return Err(Error::ReferralFeeNotPaid);
+        }
+    }
+
+    Ok(())
+}
+
+fn add_user_received(
+    user_received: &mut Vec<([u8; 32], Assets)>,
+    user_lock_hash: [u8; 32],
+    assets: Assets,
+) {
+    match user_received
+        .iter_mut()
+        .find(|(hash, _)| hash == &user_lock_hash)
+    {
+        Some((_, received)) => received.merge(assets),
+        None => user_received.push((user_lock_hash, assets)),
+    }
+}
+
+// Each user must receive assets from all its matched orders in the transaction. Completed order
+// outputs are counted, so a user cell can't be shared by different orders.
+fn validate_user_received(user_received: &[([u8; 32], Assets)]) -> Result<(), Error> {
+    for (user_lock_hash, assets) in user_received.iter() {
+        if !received_at_least(user_lock_hash, assets)? {
+            return Err(Error::QuoteSudtNotReceived);
+        }
+    }
+
+    Ok(())
+}
+
+// Cells of given lock hash receive capacity and sudt amount of its output cells minus those of
+// its input cells.
+fn received_at_least(lock_hash: &[u8; 32], assets: &Assets) -> Result<bool, Error> {
+    let inputs = load_cells_by_lock_hash(lock_hash, Source::Input)?;
+    let outputs = load_cells_by_lock_hash(lock_hash, Source::Output)?;
+
+    let received_ckb = total_capacity(&outputs).saturating_sub(total_capacity(&inputs));
+    if received_ckb < assets.ckb {
+        return Ok(false);
+    }
+
+    for (type_hash, amount) in assets.sudt.iter() {
+        let received_sudt = {
+            let output_sudt_amount = total_sudt_amount(&outputs, type_hash)?;
+            output_sudt_amount.saturating_sub(total_sudt_amount(&inputs, type_hash)?)
         };
+        if received_sudt < *amount {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+struct FeeConfig {
+    collector_lock_hash: [u8; FEE_COLLECTOR_LOCK_HASH_LEN],
+    maker_fee_rate:      Option<i16>,
+}
+
+// Fee config cell data is fee collector lock hash, optionally followed by maker fee rate(int16,
+// measured in 1/10000). Negative maker fee rate is a rebate.
+fn load_fee_config() -> Result<FeeConfig, Error> {
+    let config_position = QueryIter::new(load_cell_type_hash, Source::CellDep)
+        .position(|type_hash| type_hash == Some(FEE_CONFIG_TYPE_HASH))
+        .ok_or(Error::FeeConfigCellDepNotFound)?;
+
+    let config_data = load_cell_data(config_position, Source::CellDep)?;
+    let maker_fee_rate = match config_data.len() {
+        FEE_COLLECTOR_LOCK_HASH_LEN => None,
+        len if len == FEE_COLLECTOR_LOCK_HASH_LEN + MAKER_FEE_RATE_LEN => {
+            let mut buf = [0u8; MAKER_FEE_RATE_LEN];
+            buf.copy_from_slice(&config_data[FEE_COLLECTOR_LOCK_HASH_LEN..]);
+            let maker_fee_rate = i16::from_le_bytes(buf);
+            if i32::from(maker_fee_rate).abs() as u128 >= FEE_DECIMAL {
+                return Err(Error::MakerFeeRateOutOfRange);
+            }
+            Some(maker_fee_rate)
+        }
+        _ => return Err(Error::WrongFeeConfigDataSize),
+    };
+
+    let mut collector_lock_hash = [0u8; FEE_COLLECTOR_LOCK_HASH_LEN];
+    collector_lock_hash.copy_from_slice(&config_data[..FEE_COLLECTOR_LOCK_HASH_LEN]);
+    Ok(FeeConfig {
+        collector_lock_hash,
+        maker_fee_rate,
+    })
+}
+
+fn load_cells_by_lock_hash(lock_hash: &[u8; 32], source: Source) -> Result<Vec<Cell>, Error> {
+    QueryIter::new(load_cell_lock_hash, source)
+        .enumerate()
+        .filter(|(_, cell_lock_hash)| cell_lock_hash == lock_hash)
+        .map(|(index, _)| Cell::load(index, source).map_err(Error::from))
+        .collect()
+}
+
+fn total_capacity(cells: &[Cell]) -> u128 {
+    cells.iter().map(|cell| u128::from(cell.capacity)).sum()
+}
+
+fn total_sudt_amount(cells: &[Cell], type_hash: &[u8; 32]) -> Result<u128, Error> {
+    let mut amount = 0u128;
+    for cell in cells {
+        if cell.data.len() >= 16 && cell.type_hash()?.as_ref() == Some(type_hash) {
+            amount = amount.saturating_add(cell.sudt_amount());
+        }
+    }
+
+    Ok(amount)
+}
 
-        Ok(Price { effect, exponent })
+// Decimal price is `effect * 10^exponent`, version 3 order uses rational price
+// `numerator / denominator`, which can express prices like 1/3 exactly.
+#[derive(Debug, PartialEq, Eq)]
+enum Price {
+    Decimal { effect: u64, exponent: i8 },
+    Rational { numerator: u64, denominator: u64 },
+}
+
+impl TryFrom<RawPrice> for Price {
+    type Error = Error;
+
+    fn try_from(raw_price: RawPrice) -> Result<Price, Self::Error> {
+        match raw_price {
+            RawPrice::Decimal { effect, exponent } => {
+                if effect == 0 {
+                    return Err(Error::OrderPriceIsZero);
+                }
+
+                if exponent < PRICE_EXPONENT_MIN || exponent > PRICE_EXPONENT_MAX {
+                    return Err(Error::PriceExponentOutOfRange);
+                }
+
+                // Trailing zeros of effect must be moved into exponent
+                if effect % 10 == 0 && exponent < PRICE_EXPONENT_MAX {
+                    return Err(Error::PriceNotNormalized);
+                }
+
+                Ok(Price::Decimal { effect, exponent })
+            }
+            RawPrice::Rational {
+                numerator,
+                denominator,
+            } => {
+                if numerator == 0 {
+                    return Err(Error::OrderPriceIsZero);
+                }
+
+                if denominator == 0 {
+                    return Err(Error::PriceDenominatorIsZero);
+                }
+
+                // Fraction must be reduced, so every price has exactly one encoding
+                if gcd(numerator, denominator) != 1 {
+                    return Err(Error::PriceNotNormalized);
+                }
+
+                Ok(Price::Rational {
+                    numerator,
+                    denominator,
+                })
+            }
+        }
     }
 }
 
 impl Price {
-    fn is_exponent_negative(&self) -> bool {
-        self.exponent < 0
+    // Multiply by price numerator. Price inequalities are cross multiplied, so there's no
+    // rounding.
+    fn mul_numerator(&self, product: Product) -> Product {
+        match self {
+            Price::Decimal { effect, exponent } if *exponent < 0 => product.mul(*effect),
+            Price::Decimal { effect, exponent } => {
+                product.mul(*effect).mul_pow10(abs_exponent(*exponent))
+            }
+            Price::Rational { numerator, .. } => product.mul(*numerator),
+        }
     }
 
-    fn biguint_exponent(&self) -> BigUint {
-        let exp = {
-            let opt_abs = self.exponent.checked_abs();
-            opt_abs.map_or_else(|| 128u32, |e| e as u32)
-        };
-        BigUint::from(10u8).pow(exp)
+    fn mul_denominator(&self, product: Product) -> Product {
+        match self {
+            Price::Decimal { exponent, .. } if *exponent < 0 => {
+                product.mul_pow10(abs_exponent(*exponent))
+            }
+            Price::Decimal { .. } => product,
+            Price::Rational { denominator, .. } => product.mul(*denominator),
+        }
     }
+}
+
+// Never overflow, exponent is bounded
+fn abs_exponent(exponent: i8) -> u32 {
+    exponent.abs() as u32
+}
 
-    fn biguint_effect(&self) -> BigUint {
-        BigUint::from(self.effect)
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        let r = a % b;
+        a = b;
+        b = r;
     }
+    a
 }
 
-impl PartialEq for Price {
-    fn eq(&self, other: &Self) -> bool {
-        self.effect == other.effect && self.exponent == other.exponent
+// Input since proves that a transaction is committed after expiry, but nothing on chain proves that
+// it's committed before expiry. So expiry only opens the refund path, an expired order can still be
+// matched until someone refunds it.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+struct Expiry(u64);
+
+impl TryFrom<u64> for Expiry {
+    type Error = Error;
+
+    fn try_from(since: u64) -> Result<Expiry, Self::Error> {
+        match since & SINCE_FLAGS_MASK {
+            SINCE_METRIC_BLOCK_NUMBER | SINCE_METRIC_TIMESTAMP => Ok(Expiry(since)),
+            _ => Err(Error::UnsupportedOrderExpiry),
+        }
     }
 }
 
-impl Eq for Price {}
+impl Expiry {
+    fn metric(&self) -> u64 {
+        self.0 & SINCE_FLAGS_MASK
+    }
+
+    fn value(&self) -> u64 {
+        self.0 & SINCE_VALUE_MASK
+    }
+
+    // Since must use the same metric as expiry, relative since is never matched
+    fn reached_by_since(&self, since: u64) -> bool {
+        since & SINCE_FLAGS_MASK == self.metric() && since & SINCE_VALUE_MASK >= self.value()
+    }
+}
 
 #[repr(u8)]
 #[derive(Debug, PartialEq, Eq)]
 enum OrderType {
     SellCKB = 0,
     BuyCKB = 1,
+    SellSudt = 2,
 }
 
 impl TryFrom<u8> for OrderType {
@@ -321,6 +1442,7 @@ impl TryFrom<u8> for OrderType {
         match byte {
             0 => Ok(OrderType::SellCKB),
             1 => Ok(OrderType::BuyCKB),
+            2 => Ok(OrderType::SellSudt),
             _ => Err(Error::UnknownOrderType),
         }
     }
@@ -333,43 +1455,53 @@ struct Order {
     order_amount: u128,
     price:        Price,
     type_:        OrderType,
+    fee_rate:     u16,
+    expiry:       Option<Expiry>,
+
+    quote_type_hash: Option<[u8; 32]>,
+    referral:        Option<Referral>,
+    witness_lock:    bool,
+    strict_cancel:   bool,
 }
 
 impl TryFrom<&[u8]> for Order {
     type Error = Error;
 
     fn try_from(cell_data: &[u8]) -> Result<Order, Self::Error> {
-        if cell_data.len() != ORDER_DATA_LEN {
-            return Err(Error::WrongOrderDataSize);
-        }
+        let order_data = OrderData::decode(cell_data)?;
 
-        let mut data_buf = [0u8; ORDER_DATA_LEN];
-        data_buf.copy_from_slice(&cell_data);
+        let fee_rate = order_data.fee_rate.unwrap_or(VERSION_1_FEE_RATE);
+        if u128::from(fee_rate) >= FEE_DECIMAL {
+            return Err(Error::OrderFeeRateTooHigh);
+        }
 
-        let mut sudt_amount_buf = [0u8; 16];
-        let mut version_buf = [0u8; 1];
-        let mut order_amount_buf = [0u8; 16];
-        let mut price_buf = [0u8; PRICE_BYTES_LEN];
-        let mut order_type_buf = [0u8; 1];
+        let price = Price::try_from(order_data.price)?;
+        let type_ = OrderType::try_from(order_data.order_type)?;
+        let expiry = match order_data.expiry {
+            Some(since) => Some(Expiry::try_from(since)?),
+            None => None,
+        };
 
-        sudt_amount_buf.copy_from_slice(&data_buf[0..16]);
-        version_buf.copy_from_slice(&data_buf[16..17]);
-        order_amount_buf.copy_from_slice(&data_buf[17..33]);
-        price_buf.copy_from_slice(&data_buf[33..42]);
-        order_type_buf.copy_from_slice(&data_buf[42..43]);
+        if let Some(referral) = order_data.referral {
+            if u128::from(referral.share) > REFERRAL_SHARE_DECIMAL {
+                return Err(Error::ReferralShareOutOfRange);
+            }
+        }
 
         let order = Order {
-            sudt_amount:  u128::from_le_bytes(sudt_amount_buf),
-            version:      u8::from_le_bytes(version_buf),
-            order_amount: u128::from_le_bytes(order_amount_buf),
-            price:        Price::try_from(price_buf)?,
-            type_:        OrderType::try_from(u8::from_le_bytes(order_type_buf))?,
+            sudt_amount: order_data.sudt_amount,
+            version: order_data.version,
+            order_amount: order_data.order_amount,
+            price,
+            type_,
+            fee_rate,
+            expiry,
+            quote_type_hash: order_data.quote_type_hash,
+            referral: order_data.referral,
+            witness_lock: order_data.witness_lock,
+            strict_cancel: order_data.strict_cancel,
         };
 
-        if order.version != VERSION {
-            return Err(Error::UnexpectedOrderVersion);
-        }
-
         Ok(order)
     }
 }
@@ -0,0 +1,120 @@
+// Fixed width 256 bits unsigned integer for price arithmetic
+//
+// Price checks multiply u128 amounts by fee rate, price effect and 10^exponent. Using fixed width
+// integer avoids heap allocations of big integer, which costs a lot of cycles.
+
+use core::cmp::Ordering;
+
+// Four u64 limbs in little endian order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct U256([u64; 4]);
+
+impl U256 {
+    pub const ONE: U256 = U256([1, 0, 0, 0]);
+    pub const ZERO: U256 = U256([0; 4]);
+
+    pub fn is_zero(&self) -> bool {
+        self == &U256::ZERO
+    }
+
+    pub fn checked_mul(self, rhs: U256) -> Option<U256> {
+        let mut result = [0u64; 4];
+
+        for i in 0..4 {
+            if self.0[i] == 0 {
+                continue;
+            }
+
+            let mut carry = 0u128;
+            for j in 0..4 {
+                let product = u128::from(self.0[i]) * u128::from(rhs.0[j]);
+                if i + j >= 4 {
+                    if product != 0 {
+                        return None;
+                    }
+                    continue;
+                }
+
+                // Never overflow, (2^64 - 1)^2 + 2 * (2^64 - 1) = 2^128 - 1
+                let sum = u128::from(result[i + j]) + product + carry;
+                result[i + j] = sum as u64;
+                carry = sum >> 64;
+            }
+
+            if carry != 0 {
+                return None;
+            }
+        }
+
+        Some(U256(result))
+    }
+
+    pub fn checked_pow10(exp: u32) -> Option<U256> {
+        let ten = U256::from(10u64);
+        let mut result = U256::ONE;
+        for _ in 0..exp {
+            result = result.checked_mul(ten)?;
+        }
+
+        Some(result)
+    }
+}
+
+impl From<u64> for U256 {
+    fn from(value: u64) -> Self {
+        U256([value, 0, 0, 0])
+    }
+}
+
+impl From<u128> for U256 {
+    fn from(value: u128) -> Self {
+        U256([value as u64, (value >> 64) as u64, 0, 0])
+    }
+}
+
+impl Ord for U256 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.iter().rev().cmp(other.0.iter().rev())
+    }
+}
+
+impl PartialOrd for U256 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Product of price arithmetic. In every price inequality, only the side multiplied by
+// 10^exponent may exceed U256, the other side is at most 2^206. So an overflowed product is
+// always greater than the other side.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Product {
+    Value(U256),
+    Overflow,
+}
+
+impl Product {
+    pub fn new<T: Into<U256>>(value: T) -> Self {
+        Product::Value(value.into())
+    }
+
+    pub fn mul<T: Into<U256>>(self, rhs: T) -> Self {
+        let rhs = rhs.into();
+        match self {
+            _ if rhs.is_zero() => Product::Value(U256::ZERO),
+            Product::Value(value) => match value.checked_mul(rhs) {
+                Some(product) => Product::Value(product),
+                None => Product::Overflow,
+            },
+            Product::Overflow => Product::Overflow,
+        }
+    }
+
+    pub fn mul_pow10(self, exp: u32) -> Self {
+        match (self, U256::checked_pow10(exp)) {
+            (Product::Value(value), _) if value.is_zero() => self,
+            (_, Some(pow10)) => self.mul(pow10),
+            (_, None) => Product::Overflow,
+        }
+    }
+}
@@ -1,12 +1,16 @@
+#[path = "../../contracts/asset-order-lockscript/src/constants.rs"]
+mod constants;
 #[path = "../../contracts/asset-order-lockscript/src/entry.rs"]
 mod entry;
 #[path = "../../contracts/asset-order-lockscript/src/error.rs"]
 mod error;
 #[path = "../../contracts/asset-order-lockscript/src/order_validator.rs"]
 mod order_validator;
+#[path = "../../contracts/asset-order-lockscript/src/u256.rs"]
+mod u256;
 
 fn main() {
     if let Err(err) = entry::main() {
-        std::process::exit(err as i32);
+        std::process::exit(err.code() as i32);
     }
 }
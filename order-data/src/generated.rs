@@ -0,0 +1,2 @@
+pub mod basic;
+pub mod order_data;
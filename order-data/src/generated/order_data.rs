@@ -0,0 +1,703 @@
+// Generated by Molecule 0.6.1
+
+use molecule::prelude::*;
+use super::basic::*;
+#[derive(Clone)]
+pub struct AssetOrder(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for AssetOrder {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl ::core::fmt::Debug for AssetOrder {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl ::core::fmt::Display for AssetOrder {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "sudt_amount", self.sudt_amount())?;
+        write!(f, ", {}: {}", "version", self.version())?;
+        write!(f, ", {}: {}", "order_amount", self.order_amount())?;
+        write!(f, ", {}: {}", "price_effect", self.price_effect())?;
+        write!(f, ", {}: {}", "price_exponent", self.price_exponent())?;
+        write!(f, ", {}: {}", "order_type", self.order_type())?;
+        write!(f, " }}")
+    }
+}
+impl ::core::default::Default for AssetOrder {
+    fn default() -> Self {
+        let v: Vec<u8> = vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        AssetOrder::new_unchecked(v.into())
+    }
+}
+impl AssetOrder {
+    pub const TOTAL_SIZE: usize = 43;
+    pub const FIELD_SIZES: [usize; 6] = [16, 1, 16, 8, 1, 1];
+    pub const FIELD_COUNT: usize = 6;
+    pub fn sudt_amount(&self) -> Uint128 {
+        Uint128::new_unchecked(self.0.slice(0..16))
+    }
+    pub fn version(&self) -> Byte {
+        Byte::new_unchecked(self.0.slice(16..17))
+    }
+    pub fn order_amount(&self) -> Uint128 {
+        Uint128::new_unchecked(self.0.slice(17..33))
+    }
+    pub fn price_effect(&self) -> Uint64 {
+        Uint64::new_unchecked(self.0.slice(33..41))
+    }
+    pub fn price_exponent(&self) -> Byte {
+        Byte::new_unchecked(self.0.slice(41..42))
+    }
+    pub fn order_type(&self) -> Byte {
+        Byte::new_unchecked(self.0.slice(42..43))
+    }
+    pub fn as_reader<'r>(&'r self) -> AssetOrderReader<'r> {
+        AssetOrderReader::new_unchecked(self.as_slice())
+    }
+}
+impl molecule::prelude::Entity for AssetOrder {
+    type Builder = AssetOrderBuilder;
+    const NAME: &'static str = "AssetOrder";
+    fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
+        AssetOrder(data)
+    }
+    fn as_bytes(&self) -> molecule::bytes::Bytes {
+        self.0.clone()
+    }
+    fn as_slice(&self) -> &[u8] {
+        &self.0[..]
+    }
+    fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        AssetOrderReader::from_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        AssetOrderReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn new_builder() -> Self::Builder {
+        ::core::default::Default::default()
+    }
+    fn as_builder(self) -> Self::Builder {
+        Self::new_builder()
+            .sudt_amount(self.sudt_amount())
+            .version(self.version())
+            .order_amount(self.order_amount())
+            .price_effect(self.price_effect())
+            .price_exponent(self.price_exponent())
+            .order_type(self.order_type())
+    }
+}
+#[derive(Clone, Copy)]
+pub struct AssetOrderReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for AssetOrderReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl<'r> ::core::fmt::Debug for AssetOrderReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl<'r> ::core::fmt::Display for AssetOrderReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "sudt_amount", self.sudt_amount())?;
+        write!(f, ", {}: {}", "version", self.version())?;
+        write!(f, ", {}: {}", "order_amount", self.order_amount())?;
+        write!(f, ", {}: {}", "price_effect", self.price_effect())?;
+        write!(f, ", {}: {}", "price_exponent", self.price_exponent())?;
+        write!(f, ", {}: {}", "order_type", self.order_type())?;
+        write!(f, " }}")
+    }
+}
+impl<'r> AssetOrderReader<'r> {
+    pub const TOTAL_SIZE: usize = 43;
+    pub const FIELD_SIZES: [usize; 6] = [16, 1, 16, 8, 1, 1];
+    pub const FIELD_COUNT: usize = 6;
+    pub fn sudt_amount(&self) -> Uint128Reader<'r> {
+        Uint128Reader::new_unchecked(&self.as_slice()[0..16])
+    }
+    pub fn version(&self) -> ByteReader<'r> {
+        ByteReader::new_unchecked(&self.as_slice()[16..17])
+    }
+    pub fn order_amount(&self) -> Uint128Reader<'r> {
+        Uint128Reader::new_unchecked(&self.as_slice()[17..33])
+    }
+    pub fn price_effect(&self) -> Uint64Reader<'r> {
+        Uint64Reader::new_unchecked(&self.as_slice()[33..41])
+    }
+    pub fn price_exponent(&self) -> ByteReader<'r> {
+        ByteReader::new_unchecked(&self.as_slice()[41..42])
+    }
+    pub fn order_type(&self) -> ByteReader<'r> {
+        ByteReader::new_unchecked(&self.as_slice()[42..43])
+    }
+}
+impl<'r> molecule::prelude::Reader<'r> for AssetOrderReader<'r> {
+    type Entity = AssetOrder;
+    const NAME: &'static str = "AssetOrderReader";
+    fn to_entity(&self) -> Self::Entity {
+        Self::Entity::new_unchecked(self.as_slice().to_owned().into())
+    }
+    fn new_unchecked(slice: &'r [u8]) -> Self {
+        AssetOrderReader(slice)
+    }
+    fn as_slice(&self) -> &'r [u8] {
+        self.0
+    }
+    fn verify(slice: &[u8], _compatible: bool) -> molecule::error::VerificationResult<()> {
+        use molecule::verification_error as ve;
+        let slice_len = slice.len();
+        if slice_len != Self::TOTAL_SIZE {
+            return ve!(Self, TotalSizeNotMatch, Self::TOTAL_SIZE, slice_len);
+        }
+        Ok(())
+    }
+}
+#[derive(Debug, Default)]
+pub struct AssetOrderBuilder {
+    pub(crate) sudt_amount: Uint128,
+    pub(crate) version: Byte,
+    pub(crate) order_amount: Uint128,
+    pub(crate) price_effect: Uint64,
+    pub(crate) price_exponent: Byte,
+    pub(crate) order_type: Byte,
+}
+impl AssetOrderBuilder {
+    pub const TOTAL_SIZE: usize = 43;
+    pub const FIELD_SIZES: [usize; 6] = [16, 1, 16, 8, 1, 1];
+    pub const FIELD_COUNT: usize = 6;
+    pub fn sudt_amount(mut self, v: Uint128) -> Self {
+        self.sudt_amount = v;
+        self
+    }
+    pub fn version(mut self, v: Byte) -> Self {
+        self.version = v;
+        self
+    }
+    pub fn order_amount(mut self, v: Uint128) -> Self {
+        self.order_amount = v;
+        self
+    }
+    pub fn price_effect(mut self, v: Uint64) -> Self {
+        self.price_effect = v;
+        self
+    }
+    pub fn price_exponent(mut self, v: Byte) -> Self {
+        self.price_exponent = v;
+        self
+    }
+    pub fn order_type(mut self, v: Byte) -> Self {
+        self.order_type = v;
+        self
+    }
+}
+impl molecule::prelude::Builder for AssetOrderBuilder {
+    type Entity = AssetOrder;
+    const NAME: &'static str = "AssetOrderBuilder";
+    fn expected_length(&self) -> usize {
+        Self::TOTAL_SIZE
+    }
+    fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
+        writer.write_all(self.sudt_amount.as_slice())?;
+        writer.write_all(self.version.as_slice())?;
+        writer.write_all(self.order_amount.as_slice())?;
+        writer.write_all(self.price_effect.as_slice())?;
+        writer.write_all(self.price_exponent.as_slice())?;
+        writer.write_all(self.order_type.as_slice())?;
+        Ok(())
+    }
+    fn build(&self) -> Self::Entity {
+        let mut inner = Vec::with_capacity(self.expected_length());
+        self.write(&mut inner)
+            .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
+        AssetOrder::new_unchecked(inner.into())
+    }
+}
+#[derive(Clone)]
+pub struct AssetOrderV2(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for AssetOrderV2 {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl ::core::fmt::Debug for AssetOrderV2 {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl ::core::fmt::Display for AssetOrderV2 {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "sudt_amount", self.sudt_amount())?;
+        write!(f, ", {}: {}", "version", self.version())?;
+        write!(f, ", {}: {}", "order_amount", self.order_amount())?;
+        write!(f, ", {}: {}", "price_effect", self.price_effect())?;
+        write!(f, ", {}: {}", "price_exponent", self.price_exponent())?;
+        write!(f, ", {}: {}", "order_type", self.order_type())?;
+        write!(f, ", {}: {}", "fee_rate", self.fee_rate())?;
+        write!(f, " }}")
+    }
+}
+impl ::core::default::Default for AssetOrderV2 {
+    fn default() -> Self {
+        let v: Vec<u8> = vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        AssetOrderV2::new_unchecked(v.into())
+    }
+}
+impl AssetOrderV2 {
+    pub const TOTAL_SIZE: usize = 45;
+    pub const FIELD_SIZES: [usize; 7] = [16, 1, 16, 8, 1, 1, 2];
+    pub const FIELD_COUNT: usize = 7;
+    pub fn sudt_amount(&self) -> Uint128 {
+        Uint128::new_unchecked(self.0.slice(0..16))
+    }
+    pub fn version(&self) -> Byte {
+        Byte::new_unchecked(self.0.slice(16..17))
+    }
+    pub fn order_amount(&self) -> Uint128 {
+        Uint128::new_unchecked(self.0.slice(17..33))
+    }
+    pub fn price_effect(&self) -> Uint64 {
+        Uint64::new_unchecked(self.0.slice(33..41))
+    }
+    pub fn price_exponent(&self) -> Byte {
+        Byte::new_unchecked(self.0.slice(41..42))
+    }
+    pub fn order_type(&self) -> Byte {
+        Byte::new_unchecked(self.0.slice(42..43))
+    }
+    pub fn fee_rate(&self) -> Uint16 {
+        Uint16::new_unchecked(self.0.slice(43..45))
+    }
+    pub fn as_reader<'r>(&'r self) -> AssetOrderV2Reader<'r> {
+        AssetOrderV2Reader::new_unchecked(self.as_slice())
+    }
+}
+impl molecule::prelude::Entity for AssetOrderV2 {
+    type Builder = AssetOrderV2Builder;
+    const NAME: &'static str = "AssetOrderV2";
+    fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
+        AssetOrderV2(data)
+    }
+    fn as_bytes(&self) -> molecule::bytes::Bytes {
+        self.0.clone()
+    }
+    fn as_slice(&self) -> &[u8] {
+        &self.0[..]
+    }
+    fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        AssetOrderV2Reader::from_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        AssetOrderV2Reader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn new_builder() -> Self::Builder {
+        ::core::default::Default::default()
+    }
+    fn as_builder(self) -> Self::Builder {
+        Self::new_builder()
+            .sudt_amount(self.sudt_amount())
+            .version(self.version())
+            .order_amount(self.order_amount())
+            .price_effect(self.price_effect())
+            .price_exponent(self.price_exponent())
+            .order_type(self.order_type())
+            .fee_rate(self.fee_rate())
+    }
+}
+#[derive(Clone, Copy)]
+pub struct AssetOrderV2Reader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for AssetOrderV2Reader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl<'r> ::core::fmt::Debug for AssetOrderV2Reader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl<'r> ::core::fmt::Display for AssetOrderV2Reader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "sudt_amount", self.sudt_amount())?;
+        write!(f, ", {}: {}", "version", self.version())?;
+        write!(f, ", {}: {}", "order_amount", self.order_amount())?;
+        write!(f, ", {}: {}", "price_effect", self.price_effect())?;
+        write!(f, ", {}: {}", "price_exponent", self.price_exponent())?;
+        write!(f, ", {}: {}", "order_type", self.order_type())?;
+        write!(f, ", {}: {}", "fee_rate", self.fee_rate())?;
+        write!(f, " }}")
+    }
+}
+impl<'r> AssetOrderV2Reader<'r> {
+    pub const TOTAL_SIZE: usize = 45;
+    pub const FIELD_SIZES: [usize; 7] = [16, 1, 16, 8, 1, 1, 2];
+    pub const FIELD_COUNT: usize = 7;
+    pub fn sudt_amount(&self) -> Uint128Reader<'r> {
+        Uint128Reader::new_unchecked(&self.as_slice()[0..16])
+    }
+    pub fn version(&self) -> ByteReader<'r> {
+        ByteReader::new_unchecked(&self.as_slice()[16..17])
+    }
+    pub fn order_amount(&self) -> Uint128Reader<'r> {
+        Uint128Reader::new_unchecked(&self.as_slice()[17..33])
+    }
+    pub fn price_effect(&self) -> Uint64Reader<'r> {
+        Uint64Reader::new_unchecked(&self.as_slice()[33..41])
+    }
+    pub fn price_exponent(&self) -> ByteReader<'r> {
+        ByteReader::new_unchecked(&self.as_slice()[41..42])
+    }
+    pub fn order_type(&self) -> ByteReader<'r> {
+        ByteReader::new_unchecked(&self.as_slice()[42..43])
+    }
+    pub fn fee_rate(&self) -> Uint16Reader<'r> {
+        Uint16Reader::new_unchecked(&self.as_slice()[43..45])
+    }
+}
+impl<'r> molecule::prelude::Reader<'r> for AssetOrderV2Reader<'r> {
+    type Entity = AssetOrderV2;
+    const NAME: &'static str = "AssetOrderV2Reader";
+    fn to_entity(&self) -> Self::Entity {
+        Self::Entity::new_unchecked(self.as_slice().to_owned().into())
+    }
+    fn new_unchecked(slice: &'r [u8]) -> Self {
+        AssetOrderV2Reader(slice)
+    }
+    fn as_slice(&self) -> &'r [u8] {
+        self.0
+    }
+    fn verify(slice: &[u8], _compatible: bool) -> molecule::error::VerificationResult<()> {
+        use molecule::verification_error as ve;
+        let slice_len = slice.len();
+        if slice_len != Self::TOTAL_SIZE {
+            return ve!(Self, TotalSizeNotMatch, Self::TOTAL_SIZE, slice_len);
+        }
+        Ok(())
+    }
+}
+#[derive(Debug, Default)]
+pub struct AssetOrderV2Builder {
+    pub(crate) sudt_amount: Uint128,
+    pub(crate) version: Byte,
+    pub(crate) order_amount: Uint128,
+    pub(crate) price_effect: Uint64,
+    pub(crate) price_exponent: Byte,
+    pub(crate) order_type: Byte,
+    pub(crate) fee_rate: Uint16,
+}
+impl AssetOrderV2Builder {
+    pub const TOTAL_SIZE: usize = 45;
+    pub const FIELD_SIZES: [usize; 7] = [16, 1, 16, 8, 1, 1, 2];
+    pub const FIELD_COUNT: usize = 7;
+    pub fn sudt_amount(mut self, v: Uint128) -> Self {
+        self.sudt_amount = v;
+        self
+    }
+    pub fn version(mut self, v: Byte) -> Self {
+        self.version = v;
+        self
+    }
+    pub fn order_amount(mut self, v: Uint128) -> Self {
+        self.order_amount = v;
+        self
+    }
+    pub fn price_effect(mut self, v: Uint64) -> Self {
+        self.price_effect = v;
+        self
+    }
+    pub fn price_exponent(mut self, v: Byte) -> Self {
+        self.price_exponent = v;
+        self
+    }
+    pub fn order_type(mut self, v: Byte) -> Self {
+        self.order_type = v;
+        self
+    }
+    pub fn fee_rate(mut self, v: Uint16) -> Self {
+        self.fee_rate = v;
+        self
+    }
+}
+impl molecule::prelude::Builder for AssetOrderV2Builder {
+    type Entity = AssetOrderV2;
+    const NAME: &'static str = "AssetOrderV2Builder";
+    fn expected_length(&self) -> usize {
+        Self::TOTAL_SIZE
+    }
+    fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
+        writer.write_all(self.sudt_amount.as_slice())?;
+        writer.write_all(self.version.as_slice())?;
+        writer.write_all(self.order_amount.as_slice())?;
+        writer.write_all(self.price_effect.as_slice())?;
+        writer.write_all(self.price_exponent.as_slice())?;
+        writer.write_all(self.order_type.as_slice())?;
+        writer.write_all(self.fee_rate.as_slice())?;
+        Ok(())
+    }
+    fn build(&self) -> Self::Entity {
+        let mut inner = Vec::with_capacity(self.expected_length());
+        self.write(&mut inner)
+            .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
+        AssetOrderV2::new_unchecked(inner.into())
+    }
+}
+#[derive(Clone)]
+pub struct AssetOrderV3(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for AssetOrderV3 {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl ::core::fmt::Debug for AssetOrderV3 {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl ::core::fmt::Display for AssetOrderV3 {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "sudt_amount", self.sudt_amount())?;
+        write!(f, ", {}: {}", "version", self.version())?;
+        write!(f, ", {}: {}", "order_amount", self.order_amount())?;
+        write!(f, ", {}: {}", "price_numerator", self.price_numerator())?;
+        write!(f, ", {}: {}", "price_denominator", self.price_denominator())?;
+        write!(f, ", {}: {}", "order_type", self.order_type())?;
+        write!(f, ", {}: {}", "fee_rate", self.fee_rate())?;
+        write!(f, " }}")
+    }
+}
+impl ::core::default::Default for AssetOrderV3 {
+    fn default() -> Self {
+        let v: Vec<u8> = vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        AssetOrderV3::new_unchecked(v.into())
+    }
+}
+impl AssetOrderV3 {
+    pub const TOTAL_SIZE: usize = 52;
+    pub const FIELD_SIZES: [usize; 7] = [16, 1, 16, 8, 8, 1, 2];
+    pub const FIELD_COUNT: usize = 7;
+    pub fn sudt_amount(&self) -> Uint128 {
+        Uint128::new_unchecked(self.0.slice(0..16))
+    }
+    pub fn version(&self) -> Byte {
+        Byte::new_unchecked(self.0.slice(16..17))
+    }
+    pub fn order_amount(&self) -> Uint128 {
+        Uint128::new_unchecked(self.0.slice(17..33))
+    }
+    pub fn price_numerator(&self) -> Uint64 {
+        Uint64::new_unchecked(self.0.slice(33..41))
+    }
+    pub fn price_denominator(&self) -> Uint64 {
+        Uint64::new_unchecked(self.0.slice(41..49))
+    }
+    pub fn order_type(&self) -> Byte {
+        Byte::new_unchecked(self.0.slice(49..50))
+    }
+    pub fn fee_rate(&self) -> Uint16 {
+        Uint16::new_unchecked(self.0.slice(50..52))
+    }
+    pub fn as_reader<'r>(&'r self) -> AssetOrderV3Reader<'r> {
+        AssetOrderV3Reader::new_unchecked(self.as_slice())
+    }
+}
+impl molecule::prelude::Entity for AssetOrderV3 {
+    type Builder = AssetOrderV3Builder;
+    const NAME: &'static str = "AssetOrderV3";
+    fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
+        AssetOrderV3(data)
+    }
+    fn as_bytes(&self) -> molecule::bytes::Bytes {
+        self.0.clone()
+    }
+    fn as_slice(&self) -> &[u8] {
+        &self.0[..]
+    }
+    fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        AssetOrderV3Reader::from_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        AssetOrderV3Reader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn new_builder() -> Self::Builder {
+        ::core::default::Default::default()
+    }
+    fn as_builder(self) -> Self::Builder {
+        Self::new_builder()
+            .sudt_amount(self.sudt_amount())
+            .version(self.version())
+            .order_amount(self.order_amount())
+            .price_numerator(self.price_numerator())
+            .price_denominator(self.price_denominator())
+            .order_type(self.order_type())
+            .fee_rate(self.fee_rate())
+    }
+}
+#[derive(Clone, Copy)]
+pub struct AssetOrderV3Reader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for AssetOrderV3Reader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl<'r> ::core::fmt::Debug for AssetOrderV3Reader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl<'r> ::core::fmt::Display for AssetOrderV3Reader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "sudt_amount", self.sudt_amount())?;
+        write!(f, ", {}: {}", "version", self.version())?;
+        write!(f, ", {}: {}", "order_amount", self.order_amount())?;
+        write!(f, ", {}: {}", "price_numerator", self.price_numerator())?;
+        write!(f, ", {}: {}", "price_denominator", self.price_denominator())?;
+        write!(f, ", {}: {}", "order_type", self.order_type())?;
+        write!(f, ", {}: {}", "fee_rate", self.fee_rate())?;
+        write!(f, " }}")
+    }
+}
+impl<'r> AssetOrderV3Reader<'r> {
+    pub const TOTAL_SIZE: usize = 52;
+    pub const FIELD_SIZES: [usize; 7] = [16, 1, 16, 8, 8, 1, 2];
+    pub const FIELD_COUNT: usize = 7;
+    pub fn sudt_amount(&self) -> Uint128Reader<'r> {
+        Uint128Reader::new_unchecked(&self.as_slice()[0..16])
+    }
+    pub fn version(&self) -> ByteReader<'r> {
+        ByteReader::new_unchecked(&self.as_slice()[16..17])
+    }
+    pub fn order_amount(&self) -> Uint128Reader<'r> {
+        Uint128Reader::new_unchecked(&self.as_slice()[17..33])
+    }
+    pub fn price_numerator(&self) -> Uint64Reader<'r> {
+        Uint64Reader::new_unchecked(&self.as_slice()[33..41])
+    }
+    pub fn price_denominator(&self) -> Uint64Reader<'r> {
+        Uint64Reader::new_unchecked(&self.as_slice()[41..49])
+    }
+    pub fn order_type(&self) -> ByteReader<'r> {
+        ByteReader::new_unchecked(&self.as_slice()[49..50])
+    }
+    pub fn fee_rate(&self) -> Uint16Reader<'r> {
+        Uint16Reader::new_unchecked(&self.as_slice()[50..52])
+    }
+}
+impl<'r> molecule::prelude::Reader<'r> for AssetOrderV3Reader<'r> {
+    type Entity = AssetOrderV3;
+    const NAME: &'static str = "AssetOrderV3Reader";
+    fn to_entity(&self) -> Self::Entity {
+        Self::Entity::new_unchecked(self.as_slice().to_owned().into())
+    }
+    fn new_unchecked(slice: &'r [u8]) -> Self {
+        AssetOrderV3Reader(slice)
+    }
+    fn as_slice(&self) -> &'r [u8] {
+        self.0
+    }
+    fn verify(slice: &[u8], _compatible: bool) -> molecule::error::VerificationResult<()> {
+        use molecule::verification_error as ve;
+        let slice_len = slice.len();
+        if slice_len != Self::TOTAL_SIZE {
+            return ve!(Self, TotalSizeNotMatch, Self::TOTAL_SIZE, slice_len);
+        }
+        Ok(())
+    }
+}
+#[derive(Debug, Default)]
+pub struct AssetOrderV3Builder {
+    pub(crate) sudt_amount: Uint128,
+    pub(crate) version: Byte,
+    pub(crate) order_amount: Uint128,
+    pub(crate) price_numerator: Uint64,
+    pub(crate) price_denominator: Uint64,
+    pub(crate) order_type: Byte,
+    pub(crate) fee_rate: Uint16,
+}
+impl AssetOrderV3Builder {
+    pub const TOTAL_SIZE: usize = 52;
+    pub const FIELD_SIZES: [usize; 7] = [16, 1, 16, 8, 8, 1, 2];
+    pub const FIELD_COUNT: usize = 7;
+    pub fn sudt_amount(mut self, v: Uint128) -> Self {
+        self.sudt_amount = v;
+        self
+    }
+    pub fn version(mut self, v: Byte) -> Self {
+        self.version = v;
+        self
+    }
+    pub fn order_amount(mut self, v: Uint128) -> Self {
+        self.order_amount = v;
+        self
+    }
+    pub fn price_numerator(mut self, v: Uint64) -> Self {
+        self.price_numerator = v;
+        self
+    }
+    pub fn price_denominator(mut self, v: Uint64) -> Self {
+        self.price_denominator = v;
+        self
+    }
+    pub fn order_type(mut self, v: Byte) -> Self {
+        self.order_type = v;
+        self
+    }
+    pub fn fee_rate(mut self, v: Uint16) -> Self {
+        self.fee_rate = v;
+        self
+    }
+}
+impl molecule::prelude::Builder for AssetOrderV3Builder {
+    type Entity = AssetOrderV3;
+    const NAME: &'static str = "AssetOrderV3Builder";
+    fn expected_length(&self) -> usize {
+        Self::TOTAL_SIZE
+    }
+    fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
+        writer.write_all(self.sudt_amount.as_slice())?;
+        writer.write_all(self.version.as_slice())?;
+        writer.write_all(self.order_amount.as_slice())?;
+        writer.write_all(self.price_numerator.as_slice())?;
+        writer.write_all(self.price_denominator.as_slice())?;
+        writer.write_all(self.order_type.as_slice())?;
+        writer.write_all(self.fee_rate.as_slice())?;
+        Ok(())
+    }
+    fn build(&self) -> Self::Entity {
+        let mut inner = Vec::with_capacity(self.expected_length());
+        self.write(&mut inner)
+            .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
+        AssetOrderV3::new_unchecked(inner.into())
+    }
+}
@@ -0,0 +1,329 @@
+// Asset order cell data
+//
+// Owns the molecule schema of asset order cell data, shared by the order lock contract and host
+// tools. Generated molecule code is committed, run `make schema` to regenerate it after changing
+// schemas.
+//
+// Cell data is one of the fixed size molecule structs, followed by optional fields:
+// - version 1: `AssetOrder`, 43 bytes, no optional fields
+// - version 2: `AssetOrderV2`, 45 bytes, decimal price and fee rate
+// - version 3: `AssetOrderV3`, 52 bytes, rational price and fee rate
+//
+// High bit of version byte is witness lock flag. Flagged order can only be cancelled by witness
+// providing user lock script in witness lock field, instead of input type.
+// Second high bit of version byte is strict cancel flag. Flagged order must be refunded to user
+// lock when it's cancelled.
+//
+// Version 2 and 3 optional fields, in order:
+// - quote sudt type hash: byte32, required by sell sudt order
+// - expiry: uint64
+// - referral: byte32 referrer lock hash followed by uint16 referrer share of order fee, measured in
+//   1/10000
+
+#![no_std]
+
+extern crate alloc;
+
+#[allow(dead_code)]
+pub mod generated;
+
+use alloc::vec::Vec;
+use core::convert::TryInto;
+
+use molecule::prelude::*;
+
+use generated::basic::{Uint128, Uint16, Uint64};
+use generated::order_data::{
+    AssetOrder, AssetOrderReader, AssetOrderV2, AssetOrderV2Reader, AssetOrderV3,
+    AssetOrderV3Reader,
+};
+
+pub const VERSION_1: u8 = 1;
+pub const VERSION_2: u8 = 2;
+pub const VERSION_3: u8 = 3;
+
+pub const WITNESS_LOCK_FLAG: u8 = 0x80;
+pub const STRICT_CANCEL_FLAG: u8 = 0x40;
+
+pub const ORDER_TYPE_SELL_SUDT: u8 = 2;
+
+pub const QUOTE_TYPE_HASH_LEN: usize = 32;
+pub const EXPIRY_LEN: usize = 8;
+pub const REFERRAL_LEN: usize = 34;
+
+const REFERRER_LOCK_HASH_LEN: usize = 32;
+
+// Version byte follows sudt amount in every version
+const VERSION_OFFSET: usize = 16;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    WrongDataSize,
+    UnexpectedVersion,
+    VersionFieldsMismatch,
+}
+
+// Decimal price is `effect * 10^exponent`, rational price is `numerator / denominator`. Values
+// aren't checked here, it's up to the consumer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Price {
+    Decimal { effect: u64, exponent: i8 },
+    Rational { numerator: u64, denominator: u64 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Referral {
+    pub lock_hash: [u8; REFERRER_LOCK_HASH_LEN],
+    pub share:     u16,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrderData {
+    pub sudt_amount:     u128,
+    pub version:         u8,
+    pub order_amount:    u128,
+    pub price:           Price,
+    pub order_type:      u8,
+    pub fee_rate:        Option<u16>,
+    pub quote_type_hash: Option<[u8; QUOTE_TYPE_HASH_LEN]>,
+    pub expiry:          Option<u64>,
+    pub referral:        Option<Referral>,
+    pub witness_lock:    bool,
+    pub strict_cancel:   bool,
+}
+
+impl OrderData {
+    pub fn decode(data: &[u8]) -> Result<OrderData, Error> {
+        if data.len() < AssetOrder::TOTAL_SIZE {
+            return Err(Error::WrongDataSize);
+        }
+
+        let version = data[VERSION_OFFSET] & !(WITNESS_LOCK_FLAG | STRICT_CANCEL_FLAG);
+        let mut order = match version {
+            VERSION_1 if data.len() == AssetOrder::TOTAL_SIZE => {
+                decode_v1(AssetOrderReader::new_unchecked(data))
+            }
+            VERSION_2 if data.len() >= AssetOrderV2::TOTAL_SIZE => {
+                let fixed_data = &data[..AssetOrderV2::TOTAL_SIZE];
+                decode_v2(AssetOrderV2Reader::new_unchecked(fixed_data))
+            }
+            VERSION_3 if data.len() >= AssetOrderV3::TOTAL_SIZE => {
+                let fixed_data = &data[..AssetOrderV3::TOTAL_SIZE];
+                decode_v3(AssetOrderV3Reader::new_unchecked(fixed_data))
+            }
+            VERSION_1 | VERSION_2 | VERSION_3 => return Err(Error::WrongDataSize),
+            _ => return Err(Error::UnexpectedVersion),
+        };
+        order.version = version;
+        order.witness_lock = witness_lock_flag(data);
+        order.strict_cancel = strict_cancel_flag(data);
+
+        let mut remained_data = &data[order.fixed_data_len()..];
+
+        if order.order_type == ORDER_TYPE_SELL_SUDT {
+            if remained_data.len() < QUOTE_TYPE_HASH_LEN {
+                return Err(Error::WrongDataSize);
+            }
+
+            order.quote_type_hash = Some(to_array(&remained_data[..QUOTE_TYPE_HASH_LEN]));
+            remained_data = &remained_data[QUOTE_TYPE_HASH_LEN..];
+        }
+
+        // Expiry and referral have different sizes, they're told apart by remained data length
+        if remained_data.len() == EXPIRY_LEN || remained_data.len() == EXPIRY_LEN + REFERRAL_LEN {
+            let expiry = u64::from_le_bytes(to_array(&remained_data[..EXPIRY_LEN]));
+            order.expiry = Some(expiry);
+            remained_data = &remained_data[EXPIRY_LEN..];
+        }
+
+        order.referral = match remained_data.len() {
+            0 => None,
+            REFERRAL_LEN => Some(Referral {
+                lock_hash: to_array(&remained_data[..REFERRER_LOCK_HASH_LEN]),
+                share:     u16::from_le_bytes(to_array(&remained_data[REFERRER_LOCK_HASH_LEN..])),
+            }),
+            _ => return Err(Error::WrongDataSize),
+        };
+
+        Ok(order)
+    }
+
+    // Layout is chosen by version, fields must match it: version 1 has decimal price without fee
+    // rate and optional fields, version 2 has decimal price and fee rate, version 3 has rational
+    // price and fee rate. So decoding encoded data always gives back the same order data.
+    pub fn encode(&self) -> Result<Vec<u8>, Error> {
+        let has_optional_fields =
+            self.quote_type_hash.is_some() || self.expiry.is_some() || self.referral.is_some();
+
+        let mut data = match (self.version, self.price, self.fee_rate) {
+            (VERSION_1, Price::Decimal { effect, exponent }, None) if !has_optional_fields => {
+                AssetOrder::new_builder()
+                    .sudt_amount(pack_u128(self.sudt_amount))
+                    .version(self.version_byte().into())
+                    .order_amount(pack_u128(self.order_amount))
+                    .price_effect(pack_u64(effect))
+                    .price_exponent((exponent as u8).into())
+                    .order_type(self.order_type.into())
+                    .build()
+                    .as_slice()
+                    .to_vec()
+            }
+            (VERSION_2, Price::Decimal { effect, exponent }, Some(fee_rate)) => {
+                AssetOrderV2::new_builder()
+                    .sudt_amount(pack_u128(self.sudt_amount))
+                    .version(self.version_byte().into())
+                    .order_amount(pack_u128(self.order_amount))
+                    .price_effect(pack_u64(effect))
+                    .price_exponent((exponent as u8).into())
+                    .order_type(self.order_type.into())
+                    .fee_rate(pack_u16(fee_rate))
+                    .build()
+                    .as_slice()
+                    .to_vec()
+            }
+            (
+                VERSION_3,
+                Price::Rational {
+                    numerator,
+                    denominator,
+                },
+                Some(fee_rate),
+            ) => AssetOrderV3::new_builder()
+                .sudt_amount(pack_u128(self.sudt_amount))
+                .version(self.version_byte().into())
+                .order_amount(pack_u128(self.order_amount))
+                .price_numerator(pack_u64(numerator))
+                .price_denominator(pack_u64(denominator))
+                .order_type(self.order_type.into())
+                .fee_rate(pack_u16(fee_rate))
+                .build()
+                .as_slice()
+                .to_vec(),
+            (VERSION_1, ..) | (VERSION_2, ..) | (VERSION_3, ..) => {
+                return Err(Error::VersionFieldsMismatch)
+            }
+            _ => return Err(Error::UnexpectedVersion),
+        };
+
+        if let Some(quote_type_hash) = self.quote_type_hash {
+            data.extend_from_slice(&quote_type_hash);
+        }
+        if let Some(expiry) = self.expiry {
+            data.extend_from_slice(&expiry.to_le_bytes());
+        }
+        if let Some(referral) = self.referral {
+            data.extend_from_slice(&referral.lock_hash);
+            data.extend_from_slice(&referral.share.to_le_bytes());
+        }
+
+        Ok(data)
+    }
+
+    fn version_byte(&self) -> u8 {
+        let mut version = self.version;
+        if self.witness_lock {
+            version |= WITNESS_LOCK_FLAG;
+        }
+        if self.strict_cancel {
+            version |= STRICT_CANCEL_FLAG;
+        }
+        version
+    }
+
+    fn fixed_data_len(&self) -> usize {
+        match self.version {
+            VERSION_1 => AssetOrder::TOTAL_SIZE,
+            VERSION_2 => AssetOrderV2::TOTAL_SIZE,
+            _ => AssetOrderV3::TOTAL_SIZE,
+        }
+    }
+}
+
+// Only version byte is read, so it works on order data which can't be decoded
+pub fn witness_lock_flag(data: &[u8]) -> bool {
+    data.len() > VERSION_OFFSET && data[VERSION_OFFSET] & WITNESS_LOCK_FLAG != 0
+}
+
+pub fn strict_cancel_flag(data: &[u8]) -> bool {
+    data.len() > VERSION_OFFSET && data[VERSION_OFFSET] & STRICT_CANCEL_FLAG != 0
+}
+
+fn decode_v1(reader: AssetOrderReader) -> OrderData {
+    OrderData {
+        sudt_amount:     u128::from_le_bytes(to_array(reader.sudt_amount().as_slice())),
+        version:         reader.version().as_slice()[0],
+        order_amount:    u128::from_le_bytes(to_array(reader.order_amount().as_slice())),
+        price:           Price::Decimal {
+            effect:   u64::from_le_bytes(to_array(reader.price_effect().as_slice())),
+            exponent: reader.price_exponent().as_slice()[0] as i8,
+        },
+        order_type:      reader.order_type().as_slice()[0],
+        fee_rate:        None,
+        quote_type_hash: None,
+        expiry:          None,
+        referral:        None,
+        witness_lock:    false,
+        strict_cancel:   false,
+    }
+}
+
+fn decode_v2(reader: AssetOrderV2Reader) -> OrderData {
+    OrderData {
+        sudt_amount:     u128::from_le_bytes(to_array(reader.sudt_amount().as_slice())),
+        version:         reader.version().as_slice()[0],
+        order_amount:    u128::from_le_bytes(to_array(reader.order_amount().as_slice())),
+        price:           Price::Decimal {
+            effect:   u64::from_le_bytes(to_array(reader.price_effect().as_slice())),
+            exponent: reader.price_exponent().as_slice()[0] as i8,
+        },
+        order_type:      reader.order_type().as_slice()[0],
+        fee_rate:        Some(u16::from_le_bytes(to_array(reader.fee_rate().as_slice()))),
+        quote_type_hash: None,
+        expiry:          None,
+        referral:        None,
+        witness_lock:    false,
+        strict_cancel:   false,
+    }
+}
+
+fn decode_v3(reader: AssetOrderV3Reader) -> OrderData {
+    OrderData {
+        sudt_amount:     u128::from_le_bytes(to_array(reader.sudt_amount().as_slice())),
+        version:         reader.version().as_slice()[0],
+        order_amount:    u128::from_le_bytes(to_array(reader.order_amount().as_slice())),
+        price:           Price::Rational {
+            numerator:   u64::from_le_bytes(to_array(reader.price_numerator().as_slice())),
+            denominator: u64::from_le_bytes(to_array(reader.price_denominator().as_slice())),
+        },
+        order_type:      reader.order_type().as_slice()[0],
+        fee_rate:        Some(u16::from_le_bytes(to_array(reader.fee_rate().as_slice()))),
+        quote_type_hash: None,
+        expiry:          None,
+        referral:        None,
+        witness_lock:    false,
+        strict_cancel:   false,
+    }
+}
+
+// Slice length is guaranteed by molecule struct size
+fn to_array<T>(slice: &[u8]) -> T
+where
+    for<'a> &'a [u8]: TryInto<T>,
+{
+    match slice.try_into() {
+        Ok(array) => array,
+        Err(_) => unreachable!(),
+    }
+}
+
+fn pack_u128(value: u128) -> Uint128 {
+    Uint128::new_unchecked(value.to_le_bytes().to_vec().into())
+}
+
+fn pack_u64(value: u64) -> Uint64 {
+    Uint64::new_unchecked(value.to_le_bytes().to_vec().into())
+}
+
+fn pack_u16(value: u16) -> Uint16 {
+    Uint16::new_unchecked(value.to_le_bytes().to_vec().into())
+}
@@ -7,7 +7,6 @@ use core::result::Result;
 use ckb_std::{
     ckb_constants::Source,
     ckb_types::{bytes::Bytes, prelude::*},
-    // debug,
     dynamic_loading::CKBDLContext,
     high_level::{load_script, load_witness_args},
 };
@@ -23,10 +22,7 @@ fn test_validate_blake2b_sighash_all(
 ) -> Result<(), Error> {
     let mut pubkey_hash = [0u8; 20];
     lib.validate_blake2b_sighash_all(&mut pubkey_hash)
-        .map_err(|_| {
-            // debug!("secp256k1 error {}", err_code);
-            Error::Secp256k1
-        })?;
+        .map_err(|_| Error::Secp256k1)?;
 
     // compare with expected pubkey_hash
     if &pubkey_hash[..] != expected_pubkey_hash {
@@ -35,6 +31,91 @@ fn test_validate_blake2b_sighash_all(
     Ok(())
 }
 
+// Verify the first witness lock of current group is signed by given pubkey hash, in the same way
+// as secp256k1_blake160_sighash_all lock
+pub fn validate_blake160_sighash_all(expected_pubkey_hash: &[u8]) -> Result<(), Error> {
+    unsafe {
+        let mut context = CKBDLContext::<[u8; 128 * 1024]>::new();
+        let lib = LibSecp256k1::load(&mut context);
+
+        test_validate_blake2b_sighash_all(&lib, expected_pubkey_hash)
+    }
+}
+
+// Verify signature of message is signed by given pubkey hash
+pub fn validate_blake160_signature(
+    expected_pubkey_hash: &[u8],
+    signature: &[u8],
+    message: &[u8],
+) -> Result<(), Error> {
+    unsafe {
+        let mut context = CKBDLContext::<[u8; 128 * 1024]>::new();
+        let lib = LibSecp256k1::load(&mut context);
+
+        test_validate_signature(&lib, expected_pubkey_hash, signature, message)
+    }
+}
+
+// Recover blake160 pubkey hash of each signature(65 bytes) in signatures, which all sign the same
+// message
+pub fn recover_blake160_pubkey_hashes(
+    signatures: &[u8],
+    message: &[u8],
+    pubkey_hashes: &mut [[u8; 20]],
+) -> Result<(), Error> {
+    if signatures.len() != pubkey_hashes.len() * 65 {
+        return Err(Error::WrongDataLengthOrFormat);
+    }
+
+    unsafe {
+        let mut context = CKBDLContext::<[u8; 128 * 1024]>::new();
+        let lib = LibSecp256k1::load(&mut context);
+
+        let prefilled_data = lib
+            .load_prefilled_data()
+            .map_err(|_| Error::LoadPrefilledData)?;
+        for (signature, pubkey_hash) in signatures.chunks(65).zip(pubkey_hashes.iter_mut()) {
+            let pubkey = lib
+                .recover_pubkey(&prefilled_data, signature, message)
+                .map_err(|_| Error::RecoverPubkey)?;
+
+            let mut buf = [0u8; 32];
+            let mut hasher = new_blake2b();
+            hasher.update(pubkey.as_slice());
+            hasher.finalize(&mut buf);
+            pubkey_hash.copy_from_slice(&buf[..20]);
+        }
+    }
+
+    Ok(())
+}
+
+fn test_validate_signature(
+    lib: &LibSecp256k1,
+    expected_pubkey_hash: &[u8],
+    signature: &[u8],
+    message: &[u8],
+) -> Result<(), Error> {
+    // recover pubkey_hash
+    let prefilled_data = lib
+        .load_prefilled_data()
+        .map_err(|_| Error::LoadPrefilledData)?;
+    let pubkey = lib
+        .recover_pubkey(&prefilled_data, signature, message)
+        .map_err(|_| Error::RecoverPubkey)?;
+    let pubkey_hash = {
+        let mut buf = [0u8; 32];
+        let mut hasher = new_blake2b();
+        hasher.update(pubkey.as_slice());
+        hasher.finalize(&mut buf);
+        buf
+    };
+    if expected_pubkey_hash != &pubkey_hash[..20] {
+        return Err(Error::WrongPubkey);
+    }
+    Ok(())
+}
+
 pub fn validate() -> Result<(), Error> {
     let script = load_script()?;
     let args: Bytes = script.args().unpack();
@@ -65,27 +146,7 @@ pub fn validate() -> Result<(), Error> {
             assert_eq!(witness.len(), message.len() + signature.len());
             message.copy_from_slice(&witness[..msg_len]);
             signature.copy_from_slice(&witness[msg_len..msg_len + sig_len]);
-            // recover pubkey_hash
-            let prefilled_data = lib.load_prefilled_data().map_err(|_| {
-                // debug!("load prefilled data error: {}", err);
-                Error::LoadPrefilledData
-            })?;
-            let pubkey = lib
-                .recover_pubkey(&prefilled_data, &signature, &message)
-                .map_err(|_| {
-                    // debug!("recover pubkey error: {}", err);
-                    Error::RecoverPubkey
-                })?;
-            let pubkey_hash = {
-                let mut buf = [0u8; 32];
-                let mut hasher = new_blake2b();
-                hasher.update(pubkey.as_slice());
-                hasher.finalize(&mut buf);
-                buf
-            };
-            if &args[..] != &pubkey_hash[..20] {
-                return Err(Error::WrongPubkey);
-            }
+            test_validate_signature(&lib, &args, &signature, &message)?;
         }
     }
 
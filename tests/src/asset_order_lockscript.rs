@@ -12,16 +12,30 @@ use ckb_tool::ckb_crypto::secp::{Generator, Privkey, Pubkey};
 use ckb_tool::ckb_error::assert_error_eq;
 use ckb_tool::ckb_hash::{blake2b_256, new_blake2b};
 use ckb_tool::ckb_script::{ScriptError, TransactionScriptError};
-use ckb_tool::ckb_types::core::{Capacity, DepType, TransactionBuilder, TransactionView};
+use ckb_tool::ckb_types::core::{
+    Capacity, DepType, ScriptHashType, TransactionBuilder, TransactionView, TYPE_ID_CODE_HASH,
+};
 use ckb_tool::ckb_types::packed::{self, *};
 use ckb_tool::ckb_types::{bytes::Bytes, prelude::*, H256};
 use ckb_x64_simulator::RunningSetup;
 use molecule::prelude::*;
-use schema::cell_data::AssetOrder;
+use order_data::{OrderData, Price, Referral};
 use serde_json::to_string_pretty;
 
 const MAX_CYCLES: u64 = 10000_0000;
 
+// Type id args of fee config cell, must match the one used in contract
+const FEE_CONFIG_TYPE_ID_ARGS: &str =
+    "029031e1f659fe0c3ac4434cf4b14c9c5352d1bdda54c17b8286b879c0935399";
+
+// Index of fee config cell dep in transactions built by `build_tx`
+const FEE_CONFIG_DEP_INDEX: usize = 2;
+
+// Owner update operations, put in witness output type of the first order in lock group
+const OWNER_UPDATE_MERGE: u8 = 1;
+const OWNER_UPDATE_REPRICE: u8 = 2;
+const OWNER_UPDATE_TRANSFER: u8 = 3;
+
 // Simple macro to setup simulator for coverge
 macro_rules! test_contract {
     ($case_name:ident, $body:expr) => {
@@ -48,11 +62,24 @@ macro_rules! test_contract {
 }
 
 mod cancellation;
+mod delegate;
+mod dep_group;
+mod expiry;
+mod maker_taker;
 mod order_validator;
+mod output_mapping;
+mod owner_update;
+mod ownership_transfer;
+mod price_math;
+mod rational_price;
+mod referral;
+mod strict_cancel;
+mod sudt_pair;
 
 enum OrderType {
     SellCKB = 0,
     BuyCKB = 1,
+    SellSudt = 2,
 }
 
 impl OrderType {
@@ -60,6 +87,7 @@ impl OrderType {
         match self {
             OrderType::SellCKB => 0,
             OrderType::BuyCKB => 1,
+            OrderType::SellSudt => 2,
         }
     }
 }
@@ -83,13 +111,20 @@ impl OrderCell {
 
 #[derive(Default)]
 struct OrderCellBuilder {
-    capacity:       u64,
-    sudt_amount:    u128,
-    version:        u8,
-    order_amount:   u128,
-    price_effect:   u64,
-    price_exponent: i8,
-    order_type:     u8,
+    capacity:        u64,
+    sudt_amount:     u128,
+    version:         u8,
+    order_amount:    u128,
+    price_effect:    u64,
+    price_exponent:  i8,
+    price_rational:  Option<(u64, u64)>,
+    order_type:      u8,
+    fee_rate:        u16,
+    quote_type_hash: Option<[u8; 32]>,
+    expiry:          Option<u64>,
+    referral:        Option<Referral>,
+    witness_lock:    bool,
+    strict_cancel:   bool,
 }
 
 impl OrderCellBuilder {
@@ -129,6 +164,12 @@ impl OrderCellBuilder {
         self
     }
 
+    // Version 3 order only
+    fn rational_price(mut self, numerator: u64, denominator: u64) -> Self {
+        self.price_rational = Some((numerator, denominator));
+        self
+    }
+
     fn order_type(mut self, order_type: OrderType) -> Self {
         self.order_type = order_type.to_u8();
         self
@@ -144,22 +185,81 @@ impl OrderCellBuilder {
         self
     }
 
+    fn fee_rate(mut self, fee_rate: u16) -> Self {
+        self.fee_rate = fee_rate;
+        self
+    }
+
+    fn quote_type_hash(mut self, type_hash: [u8; 32]) -> Self {
+        self.quote_type_hash = Some(type_hash);
+        self
+    }
+
+    fn expiry(mut self, since: u64) -> Self {
+        self.expiry = Some(since);
+        self
+    }
+
+    // Set witness lock flag in version byte
+    fn witness_lock(mut self) -> Self {
+        self.witness_lock = true;
+        self
+    }
+
+    // Set strict cancel flag in version byte
+    fn strict_cancel(mut self) -> Self {
+        self.strict_cancel = true;
+        self
+    }
+
+    // Version 2 and 3 order only
+    fn referral(mut self, lock_hash: [u8; 32], share: u16) -> Self {
+        self.referral = Some(Referral { lock_hash, share });
+        self
+    }
+
     fn build(self) -> OrderCell {
         let version = if self.version == 0 { 1 } else { self.version };
-        let price_exponent = self.price_exponent.to_le_bytes();
-
-        let asset_order = AssetOrder::new_builder()
-            .sudt_amount(self.sudt_amount.pack())
-            .version(version.into())
-            .order_amount(self.order_amount.pack())
-            .price_effect(self.price_effect.pack())
-            .price_exponent(price_exponent[0].into())
-            .order_type(self.order_type.into())
-            .build();
+
+        let price = match self.price_rational {
+            Some((numerator, denominator)) => Price::Rational {
+                numerator,
+                denominator,
+            },
+            None => Price::Decimal {
+                effect:   self.price_effect,
+                exponent: self.price_exponent,
+            },
+        };
+        let fee_rate = match version {
+            2 | 3 => Some(self.fee_rate),
+            _ => None,
+        };
+
+        // Unknown version can't be encoded, it's written over version 1 order data
+        let known_version = version <= 3;
+        let order_data = OrderData {
+            sudt_amount: self.sudt_amount,
+            version: if known_version { version } else { 1 },
+            order_amount: self.order_amount,
+            price,
+            order_type: self.order_type,
+            fee_rate,
+            quote_type_hash: self.quote_type_hash,
+            expiry: self.expiry,
+            referral: self.referral,
+            witness_lock: self.witness_lock,
+            strict_cancel: self.strict_cancel,
+        };
+
+        let mut data = order_data.encode().expect("encode order data");
+        if !known_version {
+            data[16] = version;
+        }
 
         OrderCell {
             capacity: Capacity::shannons(self.capacity),
-            data:     asset_order.as_bytes(),
+            data:     Bytes::from(data),
         }
     }
 }
@@ -252,18 +352,76 @@ impl OrderInput {
             witness: None,
         }
     }
+
+    // Locate order output by output index in witness output type
+    pub fn new_order_with_output_index(cell: OrderCell, output_index: u32) -> Self {
+        let output_index = Bytes::from(output_index.to_le_bytes().to_vec());
+        let witness = WitnessArgs::new_builder()
+            .output_type(Some(output_index).pack())
+            .build();
+
+        OrderInput::Order {
+            cell_deps: None,
+            cell,
+            custom_lock_args: None,
+            witness: Some(witness.as_bytes()),
+        }
+    }
+
+    // Owner update operation followed by output indexes of updated orders
+    pub fn owner_update(self, operation: u8, output_indexes: &[u32]) -> Self {
+        let witness = WitnessArgs::new_builder()
+            .output_type(Some(owner_update_witness(operation, output_indexes)).pack())
+            .build();
+
+        match self {
+            OrderInput::Order {
+                cell_deps,
+                cell,
+                custom_lock_args,
+                ..
+            } => OrderInput::Order {
+                cell_deps,
+                cell,
+                custom_lock_args,
+                witness: Some(witness.as_bytes()),
+            },
+            any_unlock => any_unlock,
+        }
+    }
+
+    // Custom order lock args, which is user lock hash
+    pub fn custom_lock_args(self, args: Bytes) -> Self {
+        match self {
+            OrderInput::Order {
+                cell_deps,
+                cell,
+                witness,
+                ..
+            } => OrderInput::Order {
+                cell_deps,
+                cell,
+                custom_lock_args: Some(args),
+                witness,
+            },
+            any_unlock => any_unlock,
+        }
+    }
 }
 
 enum OutputCell {
     PartialFilledOrder(OrderCell),
     Sudt(SudtCell),
     Free(FreeCell),
+    SudtFee(SudtCell),
+    FreeFee(FreeCell),
 }
 
 struct OrderOutput {
-    cell:             OutputCell,
-    custom_type_args: Option<Bytes>,
-    custom_lock_args: Option<Bytes>,
+    cell:                   OutputCell,
+    custom_type_args:       Option<Bytes>,
+    custom_lock_args:       Option<Bytes>,
+    custom_order_lock_args: Option<Bytes>,
 }
 
 impl OrderOutput {
@@ -279,11 +437,20 @@ impl OrderOutput {
         Self::inner_new(OutputCell::Free(cell))
     }
 
+    fn new_sudt_fee(cell: SudtCell) -> Self {
+        Self::inner_new(OutputCell::SudtFee(cell))
+    }
+
+    fn new_free_fee(cell: FreeCell) -> Self {
+        Self::inner_new(OutputCell::FreeFee(cell))
+    }
+
     fn inner_new(cell: OutputCell) -> Self {
         OrderOutput {
             cell,
             custom_type_args: None,
             custom_lock_args: None,
+            custom_order_lock_args: None,
         }
     }
 
@@ -296,15 +463,36 @@ impl OrderOutput {
         self.custom_lock_args = Some(args);
         self
     }
+
+    // Custom order lock args of partial filled order, default is user lock hash
+    fn custom_order_lock_args(mut self, args: Bytes) -> Self {
+        self.custom_order_lock_args = Some(args);
+        self
+    }
 }
 
 fn build_tx(
     context: &mut Context,
     input_orders: Vec<OrderInput>,
     output_results: Vec<OrderOutput>,
+) -> TransactionView {
+    build_tx_with_binary(
+        context,
+        "asset-order-lockscript",
+        input_orders,
+        output_results,
+    )
+}
+
+// Order lock is deployed from given binary, which can be a variant of asset order lockscript
+fn build_tx_with_binary(
+    context: &mut Context,
+    binary_name: &str,
+    input_orders: Vec<OrderInput>,
+    output_results: Vec<OrderOutput>,
 ) -> TransactionView {
     // Deploy asset order lockscript
-    let asset_lock_bin: Bytes = Loader::default().load_binary("asset-order-lockscript");
+    let asset_lock_bin: Bytes = Loader::default().load_binary(binary_name);
     let asset_lock_out_point = context.deploy_cell(asset_lock_bin);
     let asset_lock_dep = CellDep::new_builder()
         .out_point(asset_lock_out_point.clone())
@@ -324,10 +512,22 @@ fn build_tx(
     // Use always success as test sudt type contract
     let sudt_type_script = always_success_lock_script;
 
+    // Deploy fee config cell, its data is fee collector lock hash
+    let fee_collector_lock_script = context
+        .build_script(
+            &always_success_out_point,
+            Bytes::from_static(b"fee collector"),
+        )
+        .expect("fee collector lock script");
+    let fee_config_dep = {
+        let fee_collector_lock_hash = fee_collector_lock_script.calc_script_hash().as_bytes();
+        deploy_fee_config(context, fee_collector_lock_hash)
+    };
+
     // Pass idx as args to always success lock script to mock different user lock script
     let create_user_lock_script = |context: &mut Context, idx: usize| -> (Script, Bytes) {
         let user_lock_script = {
-            let args = Bytes::from(idx.to_le_bytes().to_vec());
+            let args = user_lock_args(idx);
             context
                 .build_script(&always_success_out_point, args)
                 .expect("user lock script")
@@ -402,12 +602,13 @@ fn build_tx(
     let mut outputs = vec![];
     let mut outputs_data = vec![];
     for (idx, order_result) in output_results.into_iter().enumerate() {
-        let (user_lock_script, hash) = create_user_lock_script(context, idx);
+        let (user_lock_script, _) = create_user_lock_script(context, idx);
 
         let user_lock_script = match order_result.custom_lock_args {
             Some(lock_args) => user_lock_script.as_builder().args(lock_args.pack()).build(),
             None => user_lock_script,
         };
+        let hash = user_lock_script.calc_script_hash().as_bytes();
 
         let sudt_type_script = match order_result.custom_type_args {
             Some(type_args) => {
@@ -419,8 +620,9 @@ fn build_tx(
 
         let (output, data) = match order_result.cell {
             OutputCell::PartialFilledOrder(order) => {
+                let order_lock_args = order_result.custom_order_lock_args.unwrap_or(hash);
                 let asset_lock_script = context
-                    .build_script(&asset_lock_out_point, hash)
+                    .build_script(&asset_lock_out_point, order_lock_args)
                     .expect("asset lock script");
 
                 let output = CellOutput::new_builder()
@@ -448,6 +650,23 @@ fn build_tx(
 
                 (output, free.data)
             }
+            OutputCell::SudtFee(sudt) => {
+                let output = CellOutput::new_builder()
+                    .capacity(sudt.capacity.pack())
+                    .type_(Some(sudt_type_script).pack())
+                    .lock(fee_collector_lock_script.clone())
+                    .build();
+
+                (output, sudt.data)
+            }
+            OutputCell::FreeFee(free) => {
+                let output = CellOutput::new_builder()
+                    .capacity(free.capacity.pack())
+                    .lock(fee_collector_lock_script.clone())
+                    .build();
+
+                (output, free.data)
+            }
         };
 
         outputs.push(output);
@@ -460,6 +679,7 @@ fn build_tx(
         .outputs_data(outputs_data.pack())
         .cell_dep(asset_lock_dep)
         .cell_dep(always_success_dep)
+        .cell_dep(fee_config_dep)
         .cell_deps(cell_deps)
         .witnesses(witnesses.pack())
         .build();
@@ -467,6 +687,55 @@ fn build_tx(
     tx
 }
 
+fn deploy_fee_config(context: &mut Context, fee_collector_lock_hash: Bytes) -> CellDep {
+    let type_id_args = hex::decode(FEE_CONFIG_TYPE_ID_ARGS).expect("type id args");
+    let type_id_script = Script::new_builder()
+        .code_hash(TYPE_ID_CODE_HASH.pack())
+        .hash_type(ScriptHashType::Type.into())
+        .args(Bytes::from(type_id_args).pack())
+        .build();
+
+    let fee_config_out_point = context.create_cell(
+        CellOutput::new_builder()
+            .capacity(100_00_000_000.pack())
+            .type_(Some(type_id_script).pack())
+            .build(),
+        fee_collector_lock_hash,
+    );
+
+    CellDep::new_builder()
+        .out_point(fee_config_out_point)
+        .build()
+}
+
+// Deploy a dep group cell, which expands to given cell deps
+fn deploy_dep_group(context: &mut Context, cell_deps: Vec<CellDep>) -> CellDep {
+    let out_points = OutPointVec::new_builder()
+        .set(cell_deps.into_iter().map(|dep| dep.out_point()).collect())
+        .build();
+    let dep_group_out_point = context.deploy_cell(out_points.as_bytes());
+
+    CellDep::new_builder()
+        .out_point(dep_group_out_point)
+        .dep_type(DepType::DepGroup.into())
+        .build()
+}
+
+// Replace fee config cell dep of transaction built by `build_tx`
+fn replace_fee_config_dep(tx: TransactionView, fee_config_dep: CellDep) -> TransactionView {
+    let cell_deps = tx.cell_deps().into_iter().enumerate().map(|(idx, dep)| {
+        if idx == FEE_CONFIG_DEP_INDEX {
+            fee_config_dep.clone()
+        } else {
+            dep
+        }
+    });
+
+    tx.as_advanced_builder()
+        .set_cell_deps(cell_deps.collect())
+        .build()
+}
+
 fn build_test_context(
     input_orders: Vec<OrderInput>,
     output_results: Vec<OrderOutput>,
@@ -476,6 +745,66 @@ fn build_test_context(
     (context, tx)
 }
 
+// Owner uses always success lock, order lock args are its lock hash
+fn owner_lock_args() -> Bytes {
+    user_lock_args(100)
+}
+
+// Input unlocked by always success lock, unlocks orders like anyone can pay lock
+fn build_unlock_input(lock_args: Bytes, witness: Bytes) -> OrderInput {
+    OrderInput::AnyUnlock {
+        cell_deps: None,
+        cell: FreeCell::new_with_dec(100, 8),
+        lock: always_success_script(lock_args),
+        witness,
+    }
+}
+
+fn build_owner_input() -> (OrderInput, Bytes) {
+    let owner_lock_args = owner_lock_args();
+    let owner_lock_hash = always_success_script(owner_lock_args.clone()).calc_script_hash();
+    let owner_input = build_unlock_input(owner_lock_args, WitnessArgs::default().as_bytes());
+
+    (owner_input, owner_lock_hash.as_bytes())
+}
+
+fn build_owner_order(
+    capacity: u64,
+    sudt_amount: u128,
+    order_amount: u128,
+    price: u64,
+) -> OrderCell {
+    OrderCell::builder()
+        .capacity_dec(capacity, 8)
+        .sudt_amount_dec(sudt_amount, 8)
+        .order_amount_dec(order_amount, 8)
+        .price(price, 0)
+        .order_type(OrderType::SellCKB)
+        .build()
+}
+
+// Same as script built from always success cell in context
+fn always_success_script(args: Bytes) -> Script {
+    Script::new_builder()
+        .code_hash(CellOutput::calc_data_hash(&ALWAYS_SUCCESS[..]))
+        .hash_type(ScriptHashType::Data.into())
+        .args(args.pack())
+        .build()
+}
+
+fn owner_update_witness(operation: u8, output_indexes: &[u32]) -> Bytes {
+    let mut witness = vec![operation];
+    for output_index in output_indexes {
+        witness.extend_from_slice(&output_index.to_le_bytes());
+    }
+    Bytes::from(witness)
+}
+
+// Mock user lock args, which is input index
+fn user_lock_args(idx: usize) -> Bytes {
+    Bytes::from(idx.to_le_bytes().to_vec())
+}
+
 fn tx_error(error_code: i8, input_index: usize) -> TransactionScriptError {
     ScriptError::ValidationFailure(error_code).input_lock_script(input_index)
 }
@@ -505,6 +834,35 @@ impl Secp256k1Lock {
     }
 
     fn sign_tx(tx: TransactionView, key: &Privkey) -> TransactionView {
+        Self::sign_witness(tx, &[key], WitnessArgs::default(), Bytes::new())
+    }
+
+    // Keep other fields of the first witness, such as user lock script in input type
+    fn sign_tx_with_witness(
+        tx: TransactionView,
+        key: &Privkey,
+        witness: WitnessArgs,
+    ) -> TransactionView {
+        Self::sign_witness(tx, &[key], witness, Bytes::new())
+    }
+
+    // User lock script is put in witness lock, followed by signature
+    fn sign_tx_with_script_in_lock(
+        tx: TransactionView,
+        key: &Privkey,
+        witness: WitnessArgs,
+        lock_script: &Script,
+    ) -> TransactionView {
+        Self::sign_witness(tx, &[key], witness, lock_script.as_bytes())
+    }
+
+    // Signatures of all keys are appended to lock prefix in order
+    fn sign_witness(
+        tx: TransactionView,
+        keys: &[&Privkey],
+        witness: WitnessArgs,
+        lock_prefix: Bytes,
+    ) -> TransactionView {
         const SIGNATURE_SIZE: usize = 65;
 
         let witnesses_len = tx.inputs().len();
@@ -515,10 +873,9 @@ impl Secp256k1Lock {
         blake2b.update(&tx_hash.raw_data());
 
         // digest the first witness
-        let witness = WitnessArgs::default();
         let zero_lock: Bytes = {
-            let mut buf = Vec::new();
-            buf.resize(SIGNATURE_SIZE, 0);
+            let mut buf = lock_prefix.to_vec();
+            buf.resize(lock_prefix.len() + SIGNATURE_SIZE * keys.len(), 0);
             buf.into()
         };
         let witness_for_digest = witness
@@ -532,12 +889,19 @@ impl Secp256k1Lock {
         blake2b.update(&witness_for_digest.as_bytes());
         blake2b.finalize(&mut message);
         let message = H256::from(message);
-        let sig = key.sign_recoverable(&message).expect("sign");
+        let signed_lock = {
+            let mut buf = lock_prefix.to_vec();
+            for key in keys {
+                let sig = key.sign_recoverable(&message).expect("sign");
+                buf.extend_from_slice(&sig.serialize());
+            }
+            Bytes::from(buf)
+        };
         signed_witnesses.push(
             witness
                 .clone()
                 .as_builder()
-                .lock(Some(Bytes::from(sig.serialize())).pack())
+                .lock(Some(signed_lock).pack())
                 .build()
                 .as_bytes()
                 .pack(),
@@ -558,6 +922,65 @@ impl Secp256k1Lock {
     }
 }
 
+struct MultisigLock;
+
+impl MultisigLock {
+    // Multisig lock of ckb genesis, referenced by type hash
+    const TYPE_HASH: [u8; 32] = [
+        92, 80, 105, 235, 8, 87, 239, 198, 94, 27, 202, 12, 7, 223, 52, 195, 22, 99, 179, 98, 47,
+        211, 135, 108, 135, 99, 32, 252, 150, 52, 226, 168,
+    ];
+
+    fn multisig_script(keys: &[&Privkey], require_first_n: u8, threshold: u8) -> Bytes {
+        let mut script = vec![0u8, require_first_n, threshold, keys.len() as u8];
+        for key in keys {
+            let pubkey = key.pubkey().expect("pubkey");
+            script.extend_from_slice(&Secp256k1Lock::blake160(&pubkey.serialize()));
+        }
+        script.into()
+    }
+
+    fn lock_script(multisig_script: &Bytes, since: Option<u64>) -> Script {
+        let mut args = Secp256k1Lock::blake160(multisig_script).to_vec();
+        if let Some(since) = since {
+            args.extend_from_slice(&since.to_le_bytes());
+        }
+
+        Script::new_builder()
+            .code_hash(Self::TYPE_HASH.pack())
+            .hash_type(ScriptHashType::Type.into())
+            .args(Bytes::from(args).pack())
+            .build()
+    }
+
+    // Multisig script followed by signatures in witness lock, user lock script is put in witness
+    // input type
+    fn sign_tx(
+        tx: TransactionView,
+        keys: &[&Privkey],
+        multisig_script: &Bytes,
+        lock_script: &Script,
+    ) -> TransactionView {
+        let witness = WitnessArgs::new_builder()
+            .input_type(Some(lock_script.as_bytes()).pack())
+            .build();
+        Secp256k1Lock::sign_witness(tx, keys, witness, multisig_script.clone())
+    }
+}
+
+// Secp256k1 library used by order lock to verify secp256k1_blake160_sighash_all lock natively
+struct Secp256k1Lib;
+
+impl Secp256k1Lib {
+    fn deploy(context: &mut Context) -> CellDep {
+        let lib_path = "../ckb-miscellaneous-scripts/build/secp256k1_blake2b_sighash_all_dual";
+        let lib_bin = fs::read(lib_path).expect("secp256k1_blake2b_sighash_all_dual binary");
+        let lib_out_point = context.deploy_cell(lib_bin.into());
+
+        CellDep::new_builder().out_point(lib_out_point).build()
+    }
+}
+
 struct DynLock;
 
 impl DynLock {
@@ -624,17 +1047,35 @@ fn build_mock_transaction(tx: &TransactionView, context: &Context) -> MockTransa
     let mock_cell_deps = tx
         .cell_deps()
         .into_iter()
-        .map(|cell_dep| {
-            if cell_dep.dep_type() == DepType::DepGroup.into() {
-                panic!("Implement dep group support later!");
-            }
+        .flat_map(|cell_dep| {
             let (output, data) = context.get_cell(&cell_dep.out_point()).expect("get cell");
-            MockCellDep {
+
+            // Dep group cell is kept to resolve it, followed by its expanded cells
+            let expanded_out_points = if cell_dep.dep_type() == DepType::DepGroup.into() {
+                OutPointVec::from_slice(&data)
+                    .expect("dep group data")
+                    .into_iter()
+                    .collect()
+            } else {
+                vec![]
+            };
+
+            let mut mock_cell_deps = vec![MockCellDep {
                 cell_dep,
                 output,
                 data,
                 header: None,
+            }];
+            for out_point in expanded_out_points {
+                let (output, data) = context.get_cell(&out_point).expect("get cell");
+                mock_cell_deps.push(MockCellDep {
+                    cell_dep: CellDep::new_builder().out_point(out_point).build(),
+                    output,
+                    data,
+                    header: None,
+                });
             }
+            mock_cell_deps
         })
         .collect();
     let mock_info = MockInfo {
@@ -1,6 +1,5 @@
 use super::*;
 use ckb_dyn_lock::test_tool;
-use ckb_tool::ckb_types::core::ScriptHashType;
 
 const ERR_CANCEL_ORDER_WITHOUT_WITNESS: i8 = 25;
 const ERR_USER_LOCK_NOT_FOUND: i8 = 26;
@@ -9,9 +8,16 @@ const ERR_USER_LOCK_HASH_NOT_MATCH: i8 = 28;
 const ERR_UNKNOWN_USER_LOCK_HASH_TYPE: i8 = 29;
 const ERR_USER_LOCK_CELL_DEP_NOT_FOUND: i8 = 30;
 const ERR_DYNAMIC_LOADING_MEMORY_NOT_ENOUGH: i8 = 34;
+const ERR_INPUT_TYPE_USER_LOCK_DISABLED: i8 = 85;
+const ERR_DYNAMIC_LOCK_IN_WITNESS_LOCK: i8 = 87;
+const ERR_SECP256K1_SIGNATURE_NOT_MATCH: i8 = 92;
+const ERR_MULTISIG_SIGNATURE_NOT_MATCH: i8 = 98;
+const ERR_MULTISIG_SINCE_NOT_REACHED: i8 = 99;
 
 // secp256k1_blake160_sighash_all lock error code
 const ERR_SECP256K1_WRONG_KEY: i8 = -31;
+// secp256k1_keccak256_sighash_all lock error code, returned by order lock as it is
+const ERR_KECCAK256_WRONG_KEY: i8 = -31;
 
 // FIXME: simulator not support yet
 
@@ -195,6 +201,105 @@ fn test_directly_cancel_order_using_witness_redirect_to_forked_dyn_lock() {
         .expect("pass verification");
 }
 
+#[test]
+fn test_err_directly_cancel_order_using_signature_witness_with_wrong_key() {
+    // generate key pair
+    let privkey = Generator::random_privkey();
+    let pubkey = privkey.pubkey().expect("pubkey");
+    let eth_pubkey = DynLock::eth_pubkey(pubkey);
+    let wrong_privkey = Generator::random_privkey();
+
+    let mut context = Context::default();
+
+    // Deploy dependencies
+    let (secp256k1_keccak256_out_point, keccak256_deps) = DynLock::deploy(&mut context);
+    let keccak256_lock_script = context
+        .build_script(&secp256k1_keccak256_out_point, eth_pubkey)
+        .expect("build secp256k1 keccak256 lock script");
+
+    let order_input = {
+        let cell = OrderCell::builder()
+            .capacity_dec(1000, 8)
+            .sudt_amount(0)
+            .order_amount_dec(50, 8)
+            .price(5, 0)
+            .order_type(OrderType::SellCKB)
+            .build();
+
+        let witness = WitnessArgs::new_builder()
+            .input_type(Some(keccak256_lock_script.as_bytes()).pack())
+            .build();
+
+        OrderInput::Order {
+            cell_deps: Some(keccak256_deps),
+            cell,
+            custom_lock_args: Some(keccak256_lock_script.calc_script_hash().as_bytes()),
+            witness: Some(witness.as_bytes()),
+        }
+    };
+
+    let output = OrderOutput::new_sudt(SudtCell::new_with_dec(1020, 8, 0, 0));
+    let tx = build_tx(&mut context, vec![order_input], vec![output]);
+    let tx = context.complete_tx(tx);
+
+    // Error: sign tx use wrong key
+    let tx = test_tool::secp256k1_keccak256::sign_tx(tx, &wrong_privkey);
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_KECCAK256_WRONG_KEY, 0));
+}
+
+#[test]
+fn test_directly_cancel_order_using_witness_by_data1_hash_type() {
+    // generate key pair
+    let privkey = Generator::random_privkey();
+    let pubkey = privkey.pubkey().expect("pubkey");
+    let eth_pubkey = DynLock::eth_pubkey(pubkey);
+
+    let mut context = Context::default();
+
+    // Deploy dependencies
+    let (secp256k1_keccak256_out_point, keccak256_deps) = DynLock::deploy(&mut context);
+    let keccak256_lock_script = context
+        .build_script(&secp256k1_keccak256_out_point, eth_pubkey)
+        .expect("build secp256k1 keccak256 lock script");
+
+    // Reference user lock by data hash with data1 hash type
+    let data1_lock_script = keccak256_lock_script
+        .as_builder()
+        .hash_type(2.into())
+        .build();
+
+    let order_input = {
+        let cell = OrderCell::builder()
+            .capacity_dec(1000, 8)
+            .sudt_amount(0)
+            .order_amount_dec(50, 8)
+            .price(5, 0)
+            .order_type(OrderType::SellCKB)
+            .build();
+
+        let witness = WitnessArgs::new_builder()
+            .input_type(Some(data1_lock_script.as_bytes()).pack())
+            .build();
+
+        OrderInput::Order {
+            cell_deps: Some(keccak256_deps),
+            cell,
+            custom_lock_args: Some(data1_lock_script.calc_script_hash().as_bytes()),
+            witness: Some(witness.as_bytes()),
+        }
+    };
+
+    let output = OrderOutput::new_sudt(SudtCell::new_with_dec(1020, 8, 0, 0));
+    let tx = build_tx(&mut context, vec![order_input], vec![output]);
+    let tx = context.complete_tx(tx);
+
+    let tx = test_tool::secp256k1_keccak256::sign_tx(tx, &privkey);
+    context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+}
+
 #[test]
 fn test_cancel_order_use_secp256k1_lockscript() {
     // generate key pair
@@ -494,7 +599,7 @@ fn test_err_directly_cancel_order_unknown_user_lock_hash_type() {
     // Error: wrong hash type
     let wrong_hash_type_lcok_script = keccak256_lock_script
         .as_builder()
-        .hash_type(2.into())
+        .hash_type(3.into())
         .build();
 
     let order_input = {
@@ -680,3 +785,442 @@ fn test_err_directly_cancel_order_using_witness_dynamic_loding() {
     let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
     assert_error_eq!(err, tx_error(ERR_DYNAMIC_LOADING_MEMORY_NOT_ENOUGH, 0));
 }
+
+#[test]
+fn test_err_cancel_witness_lock_order_using_input_type() {
+    // generate key pair
+    let privkey = Generator::random_privkey();
+    let pubkey = privkey.pubkey().expect("pubkey");
+    let eth_pubkey = DynLock::eth_pubkey(pubkey);
+
+    let mut context = Context::default();
+
+    // Deploy dependencies
+    let (secp256k1_keccak256_out_point, keccak256_deps) = DynLock::deploy(&mut context);
+    let keccak256_lock_script = context
+        .build_script(&secp256k1_keccak256_out_point, eth_pubkey)
+        .expect("build secp256k1 keccak256 lock script");
+
+    let order_input = {
+        let cell = OrderCell::builder()
+            .capacity_dec(1000, 8)
+            .sudt_amount(0)
+            .order_amount_dec(50, 8)
+            .price(5, 0)
+            .order_type(OrderType::SellCKB)
+            .witness_lock()
+            .build();
+
+        // Error: witness lock flagged order requires user lock in witness lock field
+        let witness = WitnessArgs::new_builder()
+            .input_type(Some(keccak256_lock_script.as_bytes()).pack())
+            .build();
+
+        OrderInput::Order {
+            cell_deps: Some(keccak256_deps),
+            cell,
+            custom_lock_args: Some(keccak256_lock_script.calc_script_hash().as_bytes()),
+            witness: Some(witness.as_bytes()),
+        }
+    };
+
+    let output = OrderOutput::new_sudt(SudtCell::new_with_dec(1020, 8, 0, 0));
+    let tx = build_tx(&mut context, vec![order_input], vec![output]);
+    let tx = context.complete_tx(tx);
+
+    let tx = test_tool::secp256k1_keccak256::sign_tx(tx, &privkey);
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_INPUT_TYPE_USER_LOCK_DISABLED, 0));
+}
+
+#[test]
+fn test_err_directly_cancel_order_witness_lock_script_encoding() {
+    let mut context = Context::default();
+
+    // Deploy dependencies
+    let (secp256k1_keccak256_out_point, keccak256_deps) = DynLock::deploy(&mut context);
+    let keccak256_lock_script = context
+        .build_script(&secp256k1_keccak256_out_point, Default::default())
+        .expect("build secp256k1 keccak256 lock script");
+
+    let order_input = {
+        let cell = OrderCell::builder()
+            .capacity_dec(1000, 8)
+            .sudt_amount(0)
+            .order_amount_dec(50, 8)
+            .price(5, 0)
+            .order_type(OrderType::SellCKB)
+            .witness_lock()
+            .build();
+
+        // Error: witness lock is shorter than molecule header
+        let witness = WitnessArgs::new_builder()
+            .lock(Some(Bytes::from(vec![1u8, 0])).pack())
+            .build();
+
+        OrderInput::Order {
+            cell_deps: Some(keccak256_deps),
+            cell,
+            custom_lock_args: Some(keccak256_lock_script.calc_script_hash().as_bytes()),
+            witness: Some(witness.as_bytes()),
+        }
+    };
+
+    let output = OrderOutput::new_sudt(SudtCell::new_with_dec(1020, 8, 0, 0));
+    let tx = build_tx(&mut context, vec![order_input], vec![output]);
+    let tx = context.complete_tx(tx);
+
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_USER_LOCK_SCRIPT_ENCODING, 0));
+}
+
+#[test]
+fn test_err_directly_cancel_order_witness_lock_hash_not_match() {
+    let mut context = Context::default();
+
+    // Deploy dependencies
+    let (secp256k1_keccak256_out_point, keccak256_deps) = DynLock::deploy(&mut context);
+    let keccak256_lock_script = context
+        .build_script(&secp256k1_keccak256_out_point, Default::default())
+        .expect("build secp256k1 keccak256 lock script");
+
+    // Error: pass another lock script
+    let another_lock_script = context
+        .build_script(&secp256k1_keccak256_out_point, Bytes::from(vec![1u8; 20]))
+        .expect("build another lock script");
+
+    let order_input = {
+        let cell = OrderCell::builder()
+            .capacity_dec(1000, 8)
+            .sudt_amount(0)
+            .order_amount_dec(50, 8)
+            .price(5, 0)
+            .order_type(OrderType::SellCKB)
+            .witness_lock()
+            .build();
+
+        // User lock script followed by signature payload
+        let mut lock = another_lock_script.as_bytes().to_vec();
+        lock.extend_from_slice(&[0u8; 65]);
+        let witness = WitnessArgs::new_builder()
+            .lock(Some(Bytes::from(lock)).pack())
+            .build();
+
+        OrderInput::Order {
+            cell_deps: Some(keccak256_deps),
+            cell,
+            custom_lock_args: Some(keccak256_lock_script.calc_script_hash().as_bytes()),
+            witness: Some(witness.as_bytes()),
+        }
+    };
+
+    let output = OrderOutput::new_sudt(SudtCell::new_with_dec(1020, 8, 0, 0));
+    let tx = build_tx(&mut context, vec![order_input], vec![output]);
+    let tx = context.complete_tx(tx);
+
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_USER_LOCK_HASH_NOT_MATCH, 0));
+}
+
+#[test]
+fn test_err_directly_cancel_witness_lock_order_using_dyn_lock() {
+    let privkey = Generator::random_privkey();
+    let pubkey = privkey.pubkey().expect("pubkey");
+    let eth_pubkey = DynLock::eth_pubkey(pubkey);
+
+    let mut context = Context::default();
+
+    // Deploy dependencies
+    let (secp256k1_keccak256_out_point, keccak256_deps) = DynLock::deploy(&mut context);
+    let keccak256_lock_script = context
+        .build_script(&secp256k1_keccak256_out_point, eth_pubkey)
+        .expect("build secp256k1 keccak256 lock script");
+
+    let order_input = {
+        let cell = OrderCell::builder()
+            .capacity_dec(1000, 8)
+            .sudt_amount(0)
+            .order_amount_dec(50, 8)
+            .price(5, 0)
+            .order_type(OrderType::SellCKB)
+            .witness_lock()
+            .build();
+
+        // Error: dynamically loaded lock can't verify signature payload after its script
+        let mut lock = keccak256_lock_script.as_bytes().to_vec();
+        lock.extend_from_slice(&[0u8; 65]);
+        let witness = WitnessArgs::new_builder()
+            .lock(Some(Bytes::from(lock)).pack())
+            .build();
+
+        OrderInput::Order {
+            cell_deps: Some(keccak256_deps),
+            cell,
+            custom_lock_args: Some(keccak256_lock_script.calc_script_hash().as_bytes()),
+            witness: Some(witness.as_bytes()),
+        }
+    };
+
+    let output = OrderOutput::new_sudt(SudtCell::new_with_dec(1020, 8, 0, 0));
+    let tx = build_tx(&mut context, vec![order_input], vec![output]);
+    let tx = context.complete_tx(tx);
+
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_DYNAMIC_LOCK_IN_WITNESS_LOCK, 0));
+}
+
+#[test]
+fn test_cancel_witness_lock_order_use_keccak256_lockscript() {
+    let privkey = Generator::random_privkey();
+    let pubkey = privkey.pubkey().expect("pubkey");
+    let eth_pubkey = DynLock::eth_pubkey(pubkey);
+
+    let mut context = Context::default();
+
+    // Deploy dependencies
+    let (secp256k1_keccak256_out_point, keccak256_deps) = DynLock::deploy(&mut context);
+    let keccak256_lock_script = context
+        .build_script(&secp256k1_keccak256_out_point, eth_pubkey)
+        .expect("build secp256k1 keccak256 lock script");
+
+    // Witness lock flagged order owned by pw-lock is cancelled by an input of user lock
+    let cancel_input = OrderInput::AnyUnlock {
+        cell_deps: Some(keccak256_deps),
+        cell:      FreeCell::new(100_00_000_000),
+        lock:      keccak256_lock_script.clone(),
+        witness:   WitnessArgs::default().as_bytes(),
+    };
+
+    let order_input = {
+        let cell = OrderCell::builder()
+            .capacity_dec(1000, 8)
+            .sudt_amount(0)
+            .order_amount_dec(50, 8)
+            .price(5, 0)
+            .order_type(OrderType::SellCKB)
+            .witness_lock()
+            .build();
+
+        OrderInput::Order {
+            cell_deps: None,
+            cell,
+            custom_lock_args: Some(keccak256_lock_script.calc_script_hash().as_bytes()),
+            witness: None,
+        }
+    };
+
+    let output = OrderOutput::new_sudt(SudtCell::new_with_dec(1020, 8, 0, 0));
+    let tx = build_tx(&mut context, vec![cancel_input, order_input], vec![output]);
+    let tx = context.complete_tx(tx);
+
+    let tx = test_tool::secp256k1_keccak256::sign_tx(tx, &privkey);
+    context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+}
+
+fn build_secp256k1_cancel_tx(
+    context: &mut Context,
+    privkey: &Privkey,
+    witness_lock: bool,
+) -> (TransactionView, Script) {
+    let pubkey = privkey.pubkey().expect("pubkey");
+    let pubkey_hash = Secp256k1Lock::blake160(&pubkey.serialize()).to_vec();
+
+    // Only secp256k1 library is required, user lock isn't dynamically loaded
+    let (secp256k1_lock_out_point, _secp256k1_lock_deps) = Secp256k1Lock::deploy(context);
+    let secp256k1_lock_script = context
+        .build_script(&secp256k1_lock_out_point, pubkey_hash.into())
+        .expect("secp256k1 lock script");
+    let secp256k1_lib_dep = Secp256k1Lib::deploy(context);
+
+    let order_input = {
+        let builder = OrderCell::builder()
+            .capacity_dec(1000, 8)
+            .sudt_amount(0)
+            .order_amount_dec(50, 8)
+            .price(5, 0)
+            .order_type(OrderType::SellCKB);
+        let cell = if witness_lock {
+            builder.witness_lock().build()
+        } else {
+            builder.build()
+        };
+
+        OrderInput::Order {
+            cell_deps: Some(vec![secp256k1_lib_dep]),
+            cell,
+            custom_lock_args: Some(secp256k1_lock_script.calc_script_hash().as_bytes()),
+            witness: None,
+        }
+    };
+
+    let output = OrderOutput::new_sudt(SudtCell::new_with_dec(1020, 8, 0, 0));
+    let tx = build_tx(context, vec![order_input], vec![output]);
+    (context.complete_tx(tx), secp256k1_lock_script)
+}
+
+#[test]
+fn test_directly_cancel_order_using_secp256k1_witness() {
+    let privkey = Generator::random_privkey();
+    let mut context = Context::default();
+    let (tx, secp256k1_lock_script) = build_secp256k1_cancel_tx(&mut context, &privkey, false);
+
+    let witness = WitnessArgs::new_builder()
+        .input_type(Some(secp256k1_lock_script.as_bytes()).pack())
+        .build();
+    let tx = Secp256k1Lock::sign_tx_with_witness(tx, &privkey, witness);
+    context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+}
+
+#[test]
+fn test_directly_cancel_order_using_secp256k1_witness_lock() {
+    let privkey = Generator::random_privkey();
+    let mut context = Context::default();
+    let (tx, secp256k1_lock_script) = build_secp256k1_cancel_tx(&mut context, &privkey, true);
+
+    let witness = WitnessArgs::default();
+    let tx =
+        Secp256k1Lock::sign_tx_with_script_in_lock(tx, &privkey, witness, &secp256k1_lock_script);
+    context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+}
+
+#[test]
+fn test_err_directly_cancel_order_using_secp256k1_witness_with_wrong_key() {
+    let privkey = Generator::random_privkey();
+    let wrong_privkey = Generator::random_privkey();
+    let mut context = Context::default();
+    let (tx, secp256k1_lock_script) = build_secp256k1_cancel_tx(&mut context, &privkey, true);
+
+    // Error: sign tx use wrong key
+    let witness = WitnessArgs::default();
+    let tx = Secp256k1Lock::sign_tx_with_script_in_lock(
+        tx,
+        &wrong_privkey,
+        witness,
+        &secp256k1_lock_script,
+    );
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_SECP256K1_SIGNATURE_NOT_MATCH, 0));
+}
+
+fn build_multisig_cancel_tx(
+    context: &mut Context,
+    multisig_lock_script: &Script,
+    since: u64,
+) -> TransactionView {
+    let secp256k1_lib_dep = Secp256k1Lib::deploy(context);
+
+    let order_input = {
+        let cell = OrderCell::builder()
+            .capacity_dec(1000, 8)
+            .sudt_amount(0)
+            .order_amount_dec(50, 8)
+            .price(5, 0)
+            .order_type(OrderType::SellCKB)
+            .build();
+
+        OrderInput::Order {
+            cell_deps: Some(vec![secp256k1_lib_dep]),
+            cell,
+            custom_lock_args: Some(multisig_lock_script.calc_script_hash().as_bytes()),
+            witness: None,
+        }
+    };
+
+    let output = OrderOutput::new_sudt(SudtCell::new_with_dec(1020, 8, 0, 0));
+    let tx = build_tx(context, vec![order_input], vec![output]);
+    let tx = context.complete_tx(tx);
+
+    let inputs = tx
+        .inputs()
+        .into_iter()
+        .map(|input| input.as_builder().since(since.pack()).build());
+    tx.as_advanced_builder()
+        .set_inputs(inputs.collect())
+        .build()
+}
+
+#[test]
+fn test_directly_cancel_order_using_multisig_witness() {
+    let keys: Vec<Privkey> = (0..3).map(|_| Generator::random_privkey()).collect();
+    let multisig_script = MultisigLock::multisig_script(&[&keys[0], &keys[1], &keys[2]], 1, 2);
+    let multisig_lock_script = MultisigLock::lock_script(&multisig_script, None);
+
+    let mut context = Context::default();
+    let tx = build_multisig_cancel_tx(&mut context, &multisig_lock_script, 0);
+    let tx = MultisigLock::sign_tx(
+        tx,
+        &[&keys[0], &keys[2]],
+        &multisig_script,
+        &multisig_lock_script,
+    );
+
+    context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+}
+
+#[test]
+fn test_directly_cancel_order_using_multisig_witness_with_since() {
+    let keys: Vec<Privkey> = (0..3).map(|_| Generator::random_privkey()).collect();
+    let multisig_script = MultisigLock::multisig_script(&[&keys[0], &keys[1], &keys[2]], 0, 2);
+    let multisig_lock_script = MultisigLock::lock_script(&multisig_script, Some(100));
+
+    let mut context = Context::default();
+    let tx = build_multisig_cancel_tx(&mut context, &multisig_lock_script, 101);
+    let tx = MultisigLock::sign_tx(
+        tx,
+        &[&keys[1], &keys[2]],
+        &multisig_script,
+        &multisig_lock_script,
+    );
+
+    context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+}
+
+#[test]
+fn test_err_directly_cancel_order_using_multisig_witness_without_required_key() {
+    let keys: Vec<Privkey> = (0..3).map(|_| Generator::random_privkey()).collect();
+    let multisig_script = MultisigLock::multisig_script(&[&keys[0], &keys[1], &keys[2]], 1, 2);
+    let multisig_lock_script = MultisigLock::lock_script(&multisig_script, None);
+
+    let mut context = Context::default();
+    let tx = build_multisig_cancel_tx(&mut context, &multisig_lock_script, 0);
+    // Error: first key is required to sign
+    let tx = MultisigLock::sign_tx(
+        tx,
+        &[&keys[1], &keys[2]],
+        &multisig_script,
+        &multisig_lock_script,
+    );
+
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_MULTISIG_SIGNATURE_NOT_MATCH, 0));
+}
+
+#[test]
+fn test_err_directly_cancel_order_using_multisig_witness_before_since() {
+    let keys: Vec<Privkey> = (0..3).map(|_| Generator::random_privkey()).collect();
+    let multisig_script = MultisigLock::multisig_script(&[&keys[0], &keys[1], &keys[2]], 0, 2);
+    let multisig_lock_script = MultisigLock::lock_script(&multisig_script, Some(100));
+
+    let mut context = Context::default();
+    // Error: input since is earlier than multisig lock since
+    let tx = build_multisig_cancel_tx(&mut context, &multisig_lock_script, 99);
+    let tx = MultisigLock::sign_tx(
+        tx,
+        &[&keys[0], &keys[1]],
+        &multisig_script,
+        &multisig_lock_script,
+    );
+
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_MULTISIG_SINCE_NOT_REACHED, 0));
+}
@@ -0,0 +1,170 @@
+use super::*;
+
+const ERR_CANCEL_ORDER_WITHOUT_WITNESS: i8 = 25;
+const ERR_DELEGATE_CANCELLATION_NOT_REFUNDED: i8 = 105;
+
+const DELEGATE_LOCK_ARGS: &[u8] = b"delegate";
+
+// Owner and delegate both use always success lock, order lock args are their lock hashes
+fn owner_and_delegate_lock_args() -> (Bytes, Bytes) {
+    let owner_lock_args = owner_lock_args();
+    let owner_lock_hash = always_success_script(owner_lock_args.clone()).calc_script_hash();
+    let delegate_lock_hash =
+        always_success_script(Bytes::from_static(DELEGATE_LOCK_ARGS)).calc_script_hash();
+
+    let mut order_lock_args = owner_lock_hash.as_bytes().to_vec();
+    order_lock_args.extend_from_slice(delegate_lock_hash.as_slice());
+
+    (owner_lock_args, Bytes::from(order_lock_args))
+}
+
+test_contract!(test_delegate_cancel_order, {
+    let (owner_lock_args, order_lock_args) = owner_and_delegate_lock_args();
+    let delegate_input = build_unlock_input(
+        Bytes::from_static(DELEGATE_LOCK_ARGS),
+        WitnessArgs::default().as_bytes(),
+    );
+    let order_input =
+        OrderInput::new_order(build_owner_order(1000, 20, 50, 5)).custom_lock_args(order_lock_args);
+
+    // Assets are refunded to owner
+    let refund = OrderOutput::new_sudt(SudtCell::new_with_dec(1000, 8, 20, 8))
+        .custom_lock_args(owner_lock_args);
+
+    let (mut context, tx) = build_test_context(vec![delegate_input, order_input], vec![refund]);
+    let tx = context.complete_tx(tx);
+
+    context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+
+    (context, tx)
+});
+
+test_contract!(test_owner_cancel_order_with_delegate, {
+    let (owner_lock_args, order_lock_args) = owner_and_delegate_lock_args();
+    let owner_input = build_unlock_input(owner_lock_args, WitnessArgs::default().as_bytes());
+    let order_input =
+        OrderInput::new_order(build_owner_order(1000, 20, 50, 5)).custom_lock_args(order_lock_args);
+
+    // Owner can take assets anywhere
+    let output = OrderOutput::new_sudt(SudtCell::new_with_dec(1000, 8, 20, 8))
+        .custom_lock_args(Bytes::from_static(b"anywhere"));
+
+    let (mut context, tx) = build_test_context(vec![owner_input, order_input], vec![output]);
+    let tx = context.complete_tx(tx);
+
+    context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+
+    (context, tx)
+});
+
+test_contract!(test_err_delegate_cancel_order_not_refunded, {
+    let (_, order_lock_args) = owner_and_delegate_lock_args();
+    let delegate_input = build_unlock_input(
+        Bytes::from_static(DELEGATE_LOCK_ARGS),
+        WitnessArgs::default().as_bytes(),
+    );
+    let order_input =
+        OrderInput::new_order(build_owner_order(1000, 20, 50, 5)).custom_lock_args(order_lock_args);
+
+    // Error: delegate redirects assets to itself
+    let output = OrderOutput::new_sudt(SudtCell::new_with_dec(1000, 8, 20, 8))
+        .custom_lock_args(Bytes::from_static(DELEGATE_LOCK_ARGS));
+
+    let (mut context, tx) = build_test_context(vec![delegate_input, order_input], vec![output]);
+    let tx = context.complete_tx(tx);
+
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_DELEGATE_CANCELLATION_NOT_REFUNDED, 1));
+
+    (context, tx)
+});
+
+test_contract!(test_err_delegate_cancel_order_without_witness, {
+    let (owner_lock_args, order_lock_args) = owner_and_delegate_lock_args();
+    // Error: delegate input witness is empty
+    let delegate_input = build_unlock_input(Bytes::from_static(DELEGATE_LOCK_ARGS), Bytes::new());
+    let order_input =
+        OrderInput::new_order(build_owner_order(1000, 20, 50, 5)).custom_lock_args(order_lock_args);
+
+    let refund = OrderOutput::new_sudt(SudtCell::new_with_dec(1000, 8, 20, 8))
+        .custom_lock_args(owner_lock_args);
+
+    let (mut context, tx) = build_test_context(vec![delegate_input, order_input], vec![refund]);
+    let tx = context.complete_tx(tx);
+
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_CANCEL_ORDER_WITHOUT_WITNESS, 1));
+
+    (context, tx)
+});
+
+test_contract!(test_match_delegate_order_partial_filled, {
+    let (_, order_lock_args) = owner_and_delegate_lock_args();
+    let input = OrderInput::new_order(
+        OrderCell::builder()
+            .capacity_dec(2000, 8)          // 2000 ckb
+            .sudt_amount(0)                 // 0 sudt
+            .order_amount_dec(250, 8)       // 250 sudt
+            .price(5, 0)                    // 5
+            .order_type(OrderType::SellCKB)
+            .build(),
+    )
+    .custom_lock_args(order_lock_args.clone());
+
+    // Sold 500 ckb, got 100 sudt, remain 150 sudt. Order output keeps delegate lock hash.
+    let output = OrderOutput::new_order(
+        OrderCell::builder()
+            .capacity_dec(1500, 8)
+            .sudt_amount_dec(100, 8)
+            .order_amount_dec(150, 8)
+            .price(5, 0)
+            .order_type(OrderType::SellCKB)
+            .build(),
+    )
+    .custom_order_lock_args(order_lock_args);
+
+    // Dex fee 500 * 0.003 = 1.5 ckb
+    let fee = OrderOutput::new_free_fee(FreeCell::new_with_dec(15, 7));
+
+    let (mut context, tx) = build_test_context(vec![input], vec![output, fee]);
+    let tx = context.complete_tx(tx);
+
+    context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+
+    (context, tx)
+});
+
+test_contract!(test_match_delegate_order_completed, {
+    let (owner_lock_args, order_lock_args) = owner_and_delegate_lock_args();
+    let input = OrderInput::new_order(
+        OrderCell::builder()
+            .capacity(554_00_000_004)       // 554_00_000_004
+            .sudt_amount_dec(0, 0)          // 0 sudt
+            .order_amount_dec(80, 8)        // 80 sudt
+            .price(5, 0)                    // 5
+            .order_type(OrderType::SellCKB)
+            .build(),
+    )
+    .custom_lock_args(order_lock_args);
+
+    // Sold 400 ckb, got 79.76 sudt, completed order is sent to owner
+    let output = OrderOutput::new_sudt(SudtCell::new_with_dec(154_00_000_004, 0, 79_76, 6))
+        .custom_lock_args(owner_lock_args);
+    // Dex fee 400 * 0.003 = 1.2 ckb
+    let fee = OrderOutput::new_free_fee(FreeCell::new_with_dec(12, 7));
+
+    let (mut context, tx) = build_test_context(vec![input], vec![output, fee]);
+    let tx = context.complete_tx(tx);
+
+    context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+
+    (context, tx)
+});
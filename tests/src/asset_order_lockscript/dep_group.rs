@@ -0,0 +1,85 @@
+use super::*;
+use ckb_dyn_lock::test_tool;
+
+// Replace fee config cell dep with a dep group containing it
+fn put_fee_config_in_dep_group(context: &mut Context, tx: TransactionView) -> TransactionView {
+    let fee_config_dep = tx
+        .cell_deps()
+        .get(FEE_CONFIG_DEP_INDEX)
+        .expect("fee config");
+    let dep_group = deploy_dep_group(context, vec![fee_config_dep]);
+
+    replace_fee_config_dep(tx, dep_group)
+}
+
+test_contract!(test_fee_config_in_dep_group, {
+    let input = OrderInput::new_order(
+        OrderCell::builder()
+            .capacity_dec(154, 8)           // 154 ckb
+            .sudt_amount_dec(100, 8)        // 100 sudt
+            .order_amount_dec(50, 8)        // 50 ckb
+            .price(5, -1)                   // 0.5
+            .order_type(OrderType::BuyCKB)
+            .build(),
+    );
+
+    let output = OrderOutput::new_free(FreeCell::new_with_dec(203_85, 6));
+    let fee = OrderOutput::new_sudt_fee(SudtCell::new_with_dec(142, 8, 3, 7));
+
+    let (mut context, tx) = build_test_context(vec![input], vec![output, fee]);
+    let tx = put_fee_config_in_dep_group(&mut context, tx);
+    let tx = context.complete_tx(tx);
+
+    context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+
+    (context, tx)
+});
+
+#[test]
+fn test_directly_cancel_order_using_user_lock_in_dep_group() {
+    // generate key pair
+    let privkey = Generator::random_privkey();
+    let pubkey = privkey.pubkey().expect("pubkey");
+    let eth_pubkey = DynLock::eth_pubkey(pubkey);
+
+    let mut context = Context::default();
+
+    // Deploy dependencies, user lock binary and its data are provided by dep group
+    let (secp256k1_keccak256_out_point, keccak256_deps) = DynLock::deploy(&mut context);
+    let keccak256_lock_script = context
+        .build_script(&secp256k1_keccak256_out_point, eth_pubkey)
+        .expect("build secp256k1 keccak256 lock script");
+    let keccak256_dep_group = deploy_dep_group(&mut context, keccak256_deps);
+
+    let order_input = {
+        let cell = OrderCell::builder()
+            .capacity_dec(1000, 8)
+            .sudt_amount(0)
+            .order_amount_dec(50, 8)
+            .price(5, 0)
+            .order_type(OrderType::SellCKB)
+            .build();
+
+        let witness = WitnessArgs::new_builder()
+            .input_type(Some(keccak256_lock_script.as_bytes()).pack())
+            .build();
+
+        OrderInput::Order {
+            cell_deps: Some(vec![keccak256_dep_group]),
+            cell,
+            custom_lock_args: Some(keccak256_lock_script.calc_script_hash().as_bytes()),
+            witness: Some(witness.as_bytes()),
+        }
+    };
+
+    let output = OrderOutput::new_sudt(SudtCell::new_with_dec(1020, 8, 0, 0));
+    let tx = build_tx(&mut context, vec![order_input], vec![output]);
+    let tx = context.complete_tx(tx);
+
+    let tx = test_tool::secp256k1_keccak256::sign_tx(tx, &privkey);
+    context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+}
@@ -0,0 +1,141 @@
+use super::*;
+
+const ERR_UNSUPPORTED_ORDER_EXPIRY: i8 = 50;
+const ERR_OUTPUT_ORDER_EXPIRY_CHANGED: i8 = 53;
+const ERR_EXPIRED_ORDER_NOT_REFUNDED: i8 = 54;
+
+// Absolute since flags
+const SINCE_TIMESTAMP: u64 = 0x4000_0000_0000_0000;
+const SINCE_EPOCH: u64 = 0x2000_0000_0000_0000;
+
+fn set_input_since(tx: TransactionView, index: usize, since: u64) -> TransactionView {
+    let inputs = tx.inputs().into_iter().enumerate().map(|(idx, input)| {
+        if idx == index {
+            input.as_builder().since(since.pack()).build()
+        } else {
+            input
+        }
+    });
+
+    tx.as_advanced_builder()
+        .set_inputs(inputs.collect())
+        .build()
+}
+
+fn build_expiry_order(capacity: u64, sudt: u128, order_amount: u128, expiry: u64) -> OrderCell {
+    OrderCell::builder()
+        .capacity_dec(capacity, 8)
+        .sudt_amount_dec(sudt, 8)
+        .order_amount_dec(order_amount, 8)
+        .price(5, 0)
+        .order_type(OrderType::SellCKB)
+        .version(2)
+        .fee_rate(100)
+        .expiry(expiry)
+        .build()
+}
+
+test_contract!(test_refund_expired_sell_ckb_order, {
+    // Expire at block 100
+    let input = OrderInput::new_order(build_expiry_order(2000, 0, 250, 100));
+    let output = OrderOutput::new_free(FreeCell::new_with_dec(2000, 8));
+
+    let (mut context, tx) = build_test_context(vec![input], vec![output]);
+    let tx = set_input_since(tx, 0, 100);
+    let tx = context.complete_tx(tx);
+
+    context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+
+    (context, tx)
+});
+
+test_contract!(test_refund_expired_buy_ckb_order, {
+    let expiry = SINCE_TIMESTAMP | 1_600_000_000;
+    let input = OrderInput::new_order(
+        OrderCell::builder()
+            .capacity_dec(154, 8)           // 154 ckb
+            .sudt_amount_dec(100, 8)        // 100 sudt
+            .order_amount_dec(50, 8)        // 50 ckb
+            .price(5, -1)                   // 0.5
+            .order_type(OrderType::BuyCKB)
+            .version(2)
+            .fee_rate(30)
+            .expiry(expiry)
+            .build(),
+    );
+    let output = OrderOutput::new_sudt(SudtCell::new_with_dec(154, 8, 100, 8));
+
+    let (mut context, tx) = build_test_context(vec![input], vec![output]);
+    let tx = set_input_since(tx, 0, SINCE_TIMESTAMP | 1_600_000_001);
+    let tx = context.complete_tx(tx);
+
+    context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+
+    (context, tx)
+});
+
+test_contract!(test_order_with_expiry_partial_filled, {
+    let input = OrderInput::new_order(build_expiry_order(2000, 0, 250, 100));
+
+    // Sold 505 ckb, got 100 sudt, remain 150 sudt
+    let output = OrderOutput::new_order(build_expiry_order(1495, 100, 150, 100));
+    // Dex fee 505 * 0.01 = 5.05 ckb
+    let fee = OrderOutput::new_free_fee(FreeCell::new_with_dec(505, 6));
+
+    let (mut context, tx) = build_test_context(vec![input], vec![output, fee]);
+    let tx = context.complete_tx(tx);
+
+    context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+
+    (context, tx)
+});
+
+test_contract!(test_err_expired_order_not_refunded, {
+    let input = OrderInput::new_order(build_expiry_order(2000, 0, 250, 100));
+    // Error: 1 shannon is missing
+    let output = OrderOutput::new_free(FreeCell::new(2000_00_000_000 - 1));
+
+    let (mut context, tx) = build_test_context(vec![input], vec![output]);
+    let tx = set_input_since(tx, 0, 101);
+    let tx = context.complete_tx(tx);
+
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_EXPIRED_ORDER_NOT_REFUNDED, 0));
+
+    (context, tx)
+});
+
+test_contract!(test_err_unsupported_order_expiry, {
+    // Error: epoch since isn't supported
+    let input = OrderInput::new_order(build_expiry_order(2000, 0, 250, SINCE_EPOCH | 100));
+    let output = OrderOutput::new_free(FreeCell::new_with_dec(2000, 8));
+
+    let (mut context, tx) = build_test_context(vec![input], vec![output]);
+    let tx = context.complete_tx(tx);
+
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_UNSUPPORTED_ORDER_EXPIRY, 0));
+
+    (context, tx)
+});
+
+test_contract!(test_err_output_order_expiry_changed, {
+    let input = OrderInput::new_order(build_expiry_order(2000, 0, 250, 100));
+    // Error: extend expiry to block 200
+    let output = OrderOutput::new_order(build_expiry_order(1495, 100, 150, 200));
+    let fee = OrderOutput::new_free_fee(FreeCell::new_with_dec(505, 6));
+
+    let (mut context, tx) = build_test_context(vec![input], vec![output, fee]);
+    let tx = context.complete_tx(tx);
+
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_OUTPUT_ORDER_EXPIRY_CHANGED, 0));
+
+    (context, tx)
+});
@@ -0,0 +1,184 @@
+use super::*;
+
+const ERR_UNKNOWN_ORDER_ROLE: i8 = 75;
+const ERR_MAKER_FEE_RATE_OUT_OF_RANGE: i8 = 76;
+const ERR_MAKER_WITHOUT_TAKER: i8 = 77;
+const ERR_MAKER_REBATE_EXCEEDS_FEE: i8 = 78;
+
+const ROLE_TAKER: u8 = 0;
+const ROLE_MAKER: u8 = 1;
+
+// Locate order output by output index and mark order role in witness output type
+fn new_order_with_role(cell: OrderCell, output_index: u32, role: u8) -> OrderInput {
+    let mut output_witness = output_index.to_le_bytes().to_vec();
+    output_witness.push(role);
+    let witness = WitnessArgs::new_builder()
+        .output_type(Some(Bytes::from(output_witness)).pack())
+        .build();
+
+    OrderInput::Order {
+        cell_deps: None,
+        cell,
+        custom_lock_args: None,
+        witness: Some(witness.as_bytes()),
+    }
+}
+
+// Replace fee config cell dep with one configured maker fee rate
+fn set_maker_fee_rate(context: &mut Context, tx: TransactionView, rate: i16) -> TransactionView {
+    let fee_collector_lock_script = always_success_script(Bytes::from_static(b"fee collector"));
+    let mut config_data = fee_collector_lock_script
+        .calc_script_hash()
+        .as_bytes()
+        .to_vec();
+    config_data.extend_from_slice(&rate.to_le_bytes());
+
+    let fee_config_dep = deploy_fee_config(context, Bytes::from(config_data));
+    replace_fee_config_dep(tx, fee_config_dep)
+}
+
+fn build_sell_ckb_order() -> OrderCell {
+    OrderCell::builder()
+        .capacity_dec(2000, 8)          // 2000 ckb
+        .sudt_amount_dec(50, 8)         // 50 sudt
+        .order_amount_dec(150, 8)       // 150 sudt
+        .price(52, -1)                  // 5.2
+        .order_type(OrderType::SellCKB)
+        .build()
+}
+
+fn build_buy_ckb_order(order_amount: u128, decimal: u32) -> OrderCell {
+    OrderCell::builder()
+        .capacity_dec(800, 8)           // 800 ckb
+        .sudt_amount_dec(500, 8)        // 500 sudt
+        .order_amount_dec(order_amount, decimal)
+        .price(5, 0)                    // 5
+        .order_type(OrderType::BuyCKB)
+        .build()
+}
+
+test_contract!(test_maker_pays_lower_fee_rate, {
+    let input0 = new_order_with_role(build_sell_ckb_order(), 0, ROLE_TAKER);
+    let input1 = new_order_with_role(build_buy_ckb_order(750, 8), 1, ROLE_MAKER);
+
+    // Taker sold 752.25 ckb, got 150 sudt, pays 2.25675 ckb fee at 0.3%
+    let output0 = OrderOutput::new_sudt(SudtCell::new_with_dec(1247_75, 6, 200, 8));
+    // Maker paid 150.15 sudt, bought 750 ckb, pays 0.15015 sudt fee at 0.1%
+    let output1 = OrderOutput::new_sudt(SudtCell::new_with_dec(1550, 8, 349_85, 6));
+    let fee = OrderOutput::new_sudt_fee(SudtCell::new_with_dec(2_25675, 3, 15015, 3));
+
+    let (mut context, tx) = build_test_context(vec![input0, input1], vec![output0, output1, fee]);
+    let tx = set_maker_fee_rate(&mut context, tx, 10);
+    let tx = context.complete_tx(tx);
+
+    context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+
+    (context, tx)
+});
+
+test_contract!(test_maker_rebate_paid_from_taker_fee, {
+    let input0 = new_order_with_role(build_sell_ckb_order(), 0, ROLE_TAKER);
+    let input1 = new_order_with_role(build_buy_ckb_order(750_75, 6), 1, ROLE_MAKER);
+
+    // Taker sold 752.25 ckb, got 150 sudt, pays 2.25675 ckb fee at 0.3%
+    let output0 = OrderOutput::new_sudt(SudtCell::new_with_dec(1247_75, 6, 200, 8));
+    // Maker paid 150 sudt, bought 750.75 ckb, gets 0.75 ckb rebate at 0.1%
+    let output1 = OrderOutput::new_sudt(SudtCell::new_with_dec(1550_75, 6, 350, 8));
+    // Dex fee 2.25675 - 0.75 ckb
+    let fee = OrderOutput::new_free_fee(FreeCell::new_with_dec(1_50675, 3));
+
+    let (mut context, tx) = build_test_context(vec![input0, input1], vec![output0, output1, fee]);
+    let tx = set_maker_fee_rate(&mut context, tx, -10);
+    let tx = context.complete_tx(tx);
+
+    context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+
+    (context, tx)
+});
+
+test_contract!(test_err_unknown_order_role, {
+    // Error: role should be 0 or 1
+    let input0 = new_order_with_role(build_sell_ckb_order(), 0, 2);
+    let input1 = new_order_with_role(build_buy_ckb_order(750, 8), 1, ROLE_TAKER);
+
+    let output0 = OrderOutput::new_sudt(SudtCell::new_with_dec(1247_75, 6, 200, 8));
+    let output1 = OrderOutput::new_sudt(SudtCell::new_with_dec(1550, 8, 349_55, 6));
+    let fee = OrderOutput::new_sudt_fee(SudtCell::new_with_dec(2_25675, 3, 45135, 3));
+
+    let (mut context, tx) = build_test_context(vec![input0, input1], vec![output0, output1, fee]);
+    let tx = context.complete_tx(tx);
+
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_UNKNOWN_ORDER_ROLE, 0));
+
+    (context, tx)
+});
+
+test_contract!(test_err_maker_fee_rate_out_of_range, {
+    let input0 = new_order_with_role(build_sell_ckb_order(), 0, ROLE_TAKER);
+    let input1 = new_order_with_role(build_buy_ckb_order(750, 8), 1, ROLE_MAKER);
+
+    let output0 = OrderOutput::new_sudt(SudtCell::new_with_dec(1247_75, 6, 200, 8));
+    let output1 = OrderOutput::new_sudt(SudtCell::new_with_dec(1550, 8, 349_55, 6));
+    let fee = OrderOutput::new_sudt_fee(SudtCell::new_with_dec(2_25675, 3, 45135, 3));
+
+    let (mut context, tx) = build_test_context(vec![input0, input1], vec![output0, output1, fee]);
+    // Error: maker rebate rate should be less than 100%
+    let tx = set_maker_fee_rate(&mut context, tx, -10000);
+    let tx = context.complete_tx(tx);
+
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_MAKER_FEE_RATE_OUT_OF_RANGE, 0));
+
+    (context, tx)
+});
+
+test_contract!(test_err_matched_orders_without_taker, {
+    // Error: both orders are marked as maker
+    let input0 = new_order_with_role(build_sell_ckb_order(), 0, ROLE_MAKER);
+    let input1 = new_order_with_role(build_buy_ckb_order(750_75, 6), 1, ROLE_MAKER);
+
+    let output0 = OrderOutput::new_sudt(SudtCell::new_with_dec(1247_75, 6, 200, 8));
+    let output1 = OrderOutput::new_sudt(SudtCell::new_with_dec(1550_75, 6, 350, 8));
+    let fee = OrderOutput::new_free_fee(FreeCell::new_with_dec(1_50675, 3));
+
+    let (mut context, tx) = build_test_context(vec![input0, input1], vec![output0, output1, fee]);
+    let tx = set_maker_fee_rate(&mut context, tx, -10);
+    let tx = context.complete_tx(tx);
+
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_MAKER_WITHOUT_TAKER, 0));
+
+    (context, tx)
+});
+
+test_contract!(test_err_maker_rebate_exceeds_taker_fee, {
+    let input0 = new_order_with_role(build_sell_ckb_order(), 0, ROLE_TAKER);
+    let input1 = new_order_with_role(build_buy_ckb_order(753_75, 6), 1, ROLE_MAKER);
+    // Matcher provides capacity for the extra rebate
+    let matcher_lock_args = Bytes::from_static(b"matcher");
+    let input2 = build_unlock_input(matcher_lock_args.clone(), WitnessArgs::default().as_bytes());
+
+    // Taker sold 752.25 ckb, got 150 sudt, pays 2.25675 ckb fee at 0.3%
+    let output0 = OrderOutput::new_sudt(SudtCell::new_with_dec(1247_75, 6, 200, 8));
+    // Maker paid 150 sudt, bought 753.75 ckb, gets 3.75 ckb rebate at 0.5%
+    let output1 = OrderOutput::new_sudt(SudtCell::new_with_dec(1553_75, 6, 350, 8));
+    // Matcher pays 1.5 ckb of rebate
+    let output2 =
+        OrderOutput::new_free(FreeCell::new_with_dec(98_5, 7)).custom_lock_args(matcher_lock_args);
+
+    let inputs = vec![input0, input1, input2];
+    let (mut context, tx) = build_test_context(inputs, vec![output0, output1, output2]);
+    // Error: rebate 3.75 ckb is more than taker fee 2.25675 ckb
+    let tx = set_maker_fee_rate(&mut context, tx, -50);
+    let tx = context.complete_tx(tx);
+
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_MAKER_REBATE_EXCEEDS_FEE, 0));
+
+    (context, tx)
+});
@@ -19,7 +19,14 @@ const ERR_OUTPUT_BURN_SUDT_AMOUNT: i8 = 20;
 const ERR_NEGATIVE_SUDT_DIFFERENCE: i8 = 21;
 const ERR_NEGATIVE_CAPACITY_DIFFERENCE: i8 = 22;
 const ERR_PRICE_MISMATCH: i8 = 23;
-// const ERR_ORDER_STILL_MATCHABLE: i8 = 24;
+const ERR_ORDER_STILL_MATCHABLE: i8 = 24;
+const ERR_OUTPUT_ORDER_AMOUNT_MISMATCH: i8 = 40;
+const ERR_ORDER_OVERFILLED: i8 = 41;
+const ERR_ORDER_FEE_RATE_TOO_HIGH: i8 = 42;
+const ERR_OUTPUT_ORDER_FEE_RATE_CHANGED: i8 = 43;
+const ERR_DEX_FEE_NOT_PAID: i8 = 46;
+const ERR_PRICE_EXPONENT_OUT_OF_RANGE: i8 = 70;
+const ERR_PRICE_NOT_NORMALIZED: i8 = 71;
 
 test_contract!(
     test_sell_ckb_complete_to_free_cell_since_we_cant_sell_even_one_ckb,
@@ -57,7 +64,7 @@ test_contract!(
                 .capacity(554_00_000_004)       // 554_00_000_004
                 .sudt_amount_dec(0, 0)          // 0 sudt
                 .order_amount_dec(80, 8)        // 80 sudt
-                .price(50, -1)                  // 5
+                .price(5, 0)                    // 5
                 .order_type(OrderType::SellCKB)
                 .build(),
         );
@@ -65,8 +72,10 @@ test_contract!(
         // Sold 400 ckb, got 79.76 sudt, remain 0.24 sudt, require at least 1.2 ckb, but
         // we only have 4 shannons can be sold.
         let output = OrderOutput::new_sudt(SudtCell::new_with_dec(154_00_000_004, 0, 79_76, 6));
+        // Dex fee 400 * 0.003 = 1.2 ckb
+        let fee = OrderOutput::new_free_fee(FreeCell::new_with_dec(12, 7));
 
-        let (mut context, tx) = build_test_context(vec![input], vec![output]);
+        let (mut context, tx) = build_test_context(vec![input], vec![output, fee]);
         let tx = context.complete_tx(tx);
 
         context
@@ -93,8 +102,10 @@ test_contract!(
         // Sold 400 ckb, got 79.76 sudt, remain 0.24 sudt, require at least 1.2 ckb,
         // but we only have 4 shannons can be sold.
         let output = OrderOutput::new_sudt(SudtCell::new_with_dec(154_00_000_004, 0, 79_76, 6));
+        // Dex fee 400 * 0.003 = 1.2 ckb
+        let fee = OrderOutput::new_free_fee(FreeCell::new_with_dec(12, 7));
 
-        let (mut context, tx) = build_test_context(vec![input], vec![output]);
+        let (mut context, tx) = build_test_context(vec![input], vec![output, fee]);
         let tx = context.complete_tx(tx);
 
         context
@@ -119,8 +130,10 @@ test_contract!(test_complete_buy_ckb_order_since_we_cant_buy_more, {
     // Sold 100 sudt, got 49.85 ckb, remain 0.15 ckb, require at least 0.3 sudt,
     // but we have no more sudt to sell.
     let output = OrderOutput::new_free(FreeCell::new_with_dec(203_85, 6));
+    // Dex fee 100 * 0.003 = 0.3 sudt
+    let fee = OrderOutput::new_sudt_fee(SudtCell::new_with_dec(142, 8, 3, 7));
 
-    let (mut context, tx) = build_test_context(vec![input], vec![output]);
+    let (mut context, tx) = build_test_context(vec![input], vec![output, fee]);
     let tx = context.complete_tx(tx);
 
     context
@@ -162,7 +175,10 @@ test_contract!(test_ckb_sudt_matched_order_case_0_from_deal_matcher, {
             .build(),
     );
 
-    let (mut context, tx) = build_test_context(vec![input0, input1], vec![output0, output1]);
+    // Dex fee 544.90909089 * 0.003 ckb and 30.27272727 * 0.003 sudt, exactly the spread
+    let fee = OrderOutput::new_sudt_fee(SudtCell::new(163472727, 9081818));
+
+    let (mut context, tx) = build_test_context(vec![input0, input1], vec![output0, output1, fee]);
     let tx = context.complete_tx(tx);
 
     context
@@ -208,7 +224,11 @@ test_contract!(
                 .build(),
         );
 
-        let (mut context, tx) = build_test_context(vec![input0, input1], vec![output0, output1]);
+        // Dex fee 752.25 * 0.003 = 2.25675 ckb, 150.45 * 0.003 = 0.45135 sudt
+        let fee = OrderOutput::new_sudt_fee(SudtCell::new_with_dec(2_25675, 3, 45135, 3));
+
+        let (mut context, tx) =
+            build_test_context(vec![input0, input1], vec![output0, output1, fee]);
         let tx = context.complete_tx(tx);
 
         context
@@ -219,6 +239,107 @@ test_contract!(
     }
 );
 
+test_contract!(test_sell_ckb_order_partial_filled, {
+    let input = OrderInput::new_order(
+        OrderCell::builder()
+            .capacity_dec(2000, 8)          // 2000 ckb
+            .sudt_amount(0)                 // 0 sudt
+            .order_amount_dec(250, 8)       // 250 sudt
+            .price(5, 0)                    // 5
+            .order_type(OrderType::SellCKB)
+            .build(),
+    );
+
+    // Sold 500 ckb, got 100 sudt, remain 150 sudt
+    let output = OrderOutput::new_order(
+        OrderCell::builder()
+            .capacity_dec(1500, 8)
+            .sudt_amount_dec(100, 8)
+            .order_amount_dec(150, 8)
+            .price(5, 0)
+            .order_type(OrderType::SellCKB)
+            .build(),
+    );
+
+    // Dex fee 500 * 0.003 = 1.5 ckb
+    let fee = OrderOutput::new_free_fee(FreeCell::new_with_dec(15, 7));
+
+    let (mut context, tx) = build_test_context(vec![input], vec![output, fee]);
+    let tx = context.complete_tx(tx);
+
+    context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+
+    (context, tx)
+});
+
+test_contract!(test_version_2_order_partial_filled_with_fee_rate, {
+    let input = OrderInput::new_order(
+        OrderCell::builder()
+            .capacity_dec(2000, 8)          // 2000 ckb
+            .sudt_amount(0)                 // 0 sudt
+            .order_amount_dec(250, 8)       // 250 sudt
+            .price(5, 0)                    // 5
+            .order_type(OrderType::SellCKB)
+            .version(2)
+            .fee_rate(100)                  // 1%
+            .build(),
+    );
+
+    // Sold 505 ckb, got 100 sudt, 1% fee is 5.05 ckb
+    let output = OrderOutput::new_order(
+        OrderCell::builder()
+            .capacity_dec(1495, 8)
+            .sudt_amount_dec(100, 8)
+            .order_amount_dec(150, 8)
+            .price(5, 0)
+            .order_type(OrderType::SellCKB)
+            .version(2)
+            .fee_rate(100)
+            .build(),
+    );
+
+    // Dex fee 505 * 0.01 = 5.05 ckb
+    let fee = OrderOutput::new_free_fee(FreeCell::new_with_dec(505, 6));
+
+    let (mut context, tx) = build_test_context(vec![input], vec![output, fee]);
+    let tx = context.complete_tx(tx);
+
+    context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+
+    (context, tx)
+});
+
+test_contract!(test_sell_ckb_order_overfilled_within_tolerance, {
+    let input = OrderInput::new_order(
+        OrderCell::builder()
+            .capacity_dec(2000, 8)          // 2000 ckb
+            .sudt_amount(0)                 // 0 sudt
+            .order_amount_dec(100, 8)       // 100 sudt
+            .price(5, 0)                    // 5
+            .order_type(OrderType::SellCKB)
+            .build(),
+    );
+
+    // Sold 500 ckb, got 100.05 sudt, overfilled 0.05% is within tolerance
+    let output = OrderOutput::new_sudt(SudtCell::new_with_dec(1500, 8, 100_05, 6));
+
+    // Dex fee 500 * 0.003 = 1.5 ckb
+    let fee = OrderOutput::new_free_fee(FreeCell::new_with_dec(15, 7));
+
+    let (mut context, tx) = build_test_context(vec![input], vec![output, fee]);
+    let tx = context.complete_tx(tx);
+
+    context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+
+    (context, tx)
+});
+
 test_contract!(test_ckb_sudt_completed_matched_order_pair, {
     let input0 = OrderInput::new_order(
         OrderCell::builder()
@@ -243,7 +364,10 @@ test_contract!(test_ckb_sudt_completed_matched_order_pair, {
     let output0 = OrderOutput::new_sudt(SudtCell::new_with_dec(1247_75, 6, 200, 8));
     let output1 = OrderOutput::new_sudt(SudtCell::new_with_dec(1550, 8, 349_55, 6));
 
-    let (mut context, tx) = build_test_context(vec![input0, input1], vec![output0, output1]);
+    // Dex fee 752.25 * 0.003 = 2.25675 ckb, 150.45 * 0.003 = 0.45135 sudt
+    let fee = OrderOutput::new_sudt_fee(SudtCell::new_with_dec(2_25675, 3, 45135, 3));
+
+    let (mut context, tx) = build_test_context(vec![input0, input1], vec![output0, output1, fee]);
     let tx = context.complete_tx(tx);
 
     context
@@ -300,8 +424,11 @@ test_contract!(ckb_sudt_two_completed_matched_order_pairs, {
     let output2 = OrderOutput::new_sudt(SudtCell::new_with_dec(199_40, 6, 50, 8));
     let output3 = OrderOutput::new_sudt(SudtCell::new_with_dec(600, 8, 59_88, 6));
 
+    // Dex fee 2.25675 + 0.6018 = 2.85855 ckb, 0.45135 + 0.12036 = 0.57171 sudt
+    let fee = OrderOutput::new_sudt_fee(SudtCell::new_with_dec(2_85855, 3, 57171, 3));
+
     let (mut context, tx) = build_test_context(vec![input0, input1, input2, input3], vec![
-        output0, output1, output2, output3,
+        output0, output1, output2, output3, fee,
     ]);
     let tx = context.complete_tx(tx);
 
@@ -382,6 +509,50 @@ test_contract!(test_err_order_price_is_zero, {
     (context, tx)
 });
 
+test_contract!(test_err_price_exponent_out_of_range, {
+    let input = OrderInput::new_order(
+        OrderCell::builder()
+            .capacity_dec(2000, 8)
+            .sudt_amount_dec(50, 8)
+            .order_amount_dec(150, 8)
+            .price(5, -33)                  // Error: exponent is below -32
+            .order_type(OrderType::SellCKB)
+            .build(),
+    );
+
+    let output = OrderOutput::new_sudt(SudtCell::new_with_dec(1247_75, 6, 200, 8));
+
+    let (mut context, tx) = build_test_context(vec![input], vec![output]);
+    let tx = context.complete_tx(tx);
+
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_PRICE_EXPONENT_OUT_OF_RANGE, 0));
+
+    (context, tx)
+});
+
+test_contract!(test_err_price_not_normalized, {
+    let input = OrderInput::new_order(
+        OrderCell::builder()
+            .capacity_dec(2000, 8)
+            .sudt_amount_dec(50, 8)
+            .order_amount_dec(150, 8)
+            .price(50, -1)                  // Error: should be encoded as 5 * 10^0
+            .order_type(OrderType::SellCKB)
+            .build(),
+    );
+
+    let output = OrderOutput::new_sudt(SudtCell::new_with_dec(1247_75, 6, 200, 8));
+
+    let (mut context, tx) = build_test_context(vec![input], vec![output]);
+    let tx = context.complete_tx(tx);
+
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_PRICE_NOT_NORMALIZED, 0));
+
+    (context, tx)
+});
+
 test_contract!(test_err_unknown_order_type, {
     let input = OrderInput::new_order(
         OrderCell::builder()
@@ -599,6 +770,68 @@ test_contract!(test_err_partial_filled_order_amount_is_zero, {
     (context, tx)
 });
 
+test_contract!(test_err_sell_ckb_partial_filled_order_amount_mismatch, {
+    let input = OrderInput::new_order(
+        OrderCell::builder()
+            .capacity_dec(2000, 8)
+            .sudt_amount(0)
+            .order_amount_dec(250, 8)
+            .price(5, 0)
+            .order_type(OrderType::SellCKB)
+            .build(),
+    );
+
+    // Sold 500 ckb, got 100 sudt, remain 150 sudt
+    let output = OrderOutput::new_order(
+        OrderCell::builder()
+            .capacity_dec(1500, 8)
+            .sudt_amount_dec(100, 8)
+            .order_amount_dec(200, 8)       // Error: order amount should be 150 sudt
+            .price(5, 0)
+            .order_type(OrderType::SellCKB)
+            .build(),
+    );
+
+    let (mut context, tx) = build_test_context(vec![input], vec![output]);
+    let tx = context.complete_tx(tx);
+
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_OUTPUT_ORDER_AMOUNT_MISMATCH, 0));
+
+    (context, tx)
+});
+
+test_contract!(test_err_buy_ckb_partial_filled_order_amount_mismatch, {
+    let input = OrderInput::new_order(
+        OrderCell::builder()
+            .capacity_dec(800, 8)
+            .sudt_amount_dec(500, 8)
+            .order_amount_dec(1000, 8)
+            .price(5, 0)
+            .order_type(OrderType::BuyCKB)
+            .build(),
+    );
+
+    // Bought 750 ckb, remain 250 ckb
+    let output = OrderOutput::new_order(
+        OrderCell::builder()
+            .capacity_dec(1550, 8)
+            .sudt_amount_dec(34955, 6)
+            .order_amount_dec(1000, 8)      // Error: order amount isn't decreased
+            .price(5, 0)
+            .order_type(OrderType::BuyCKB)
+            .build(),
+    );
+
+    let (mut context, tx) = build_test_context(vec![input], vec![output]);
+    let tx = context.complete_tx(tx);
+
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_OUTPUT_ORDER_AMOUNT_MISMATCH, 0));
+
+    (context, tx)
+});
+
 test_contract!(
     test_err_type_hash_changed_output_from_completed_sell_ckb_order,
     {
@@ -981,79 +1214,317 @@ test_contract!(
     }
 );
 
-// test_contract!(
-//     test_err_sell_ckb_order_still_matchable_price_exponent_is_negative,
-//     {
-//         let input = OrderInput::new_order(
-//             OrderCell::builder()
-//                 .capacity_dec(555, 8)           // 555 ckb
-//                 .sudt_amount_dec(0, 0)          // 0 sudt
-//                 .order_amount_dec(80, 8)        // 80 sudt
-//                 .price(50, -1)                  // 5
-//                 .order_type(OrderType::SellCKB)
-//                 .build(),
-//         );
-//
-//         // Sold 400 ckb, got 79.76 sudt, remain 0.24 sudt.
-//         // Error: we can still sell 1_00_000_000 shannons to buy  20_000_000 sudt.
-//         let output = OrderOutput::new_sudt(SudtCell::new_with_dec(155, 8, 79_76, 6));
-//
-//         let (mut context, tx) = build_test_context(vec![input], vec![output]);
-//         let tx = context.complete_tx(tx);
-//
-//         let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
-//         assert_error_eq!(err, tx_error(ERR_ORDER_STILL_MATCHABLE, 0));
-//
-//         (context, tx)
-//     }
-// );
-//
-// test_contract!(
-//     test_err_sell_ckb_order_still_matchable_price_exponent_is_postive,
-//     {
-//         let input = OrderInput::new_order(
-//             OrderCell::builder()
-//                 .capacity_dec(555, 8)           // 555 ckb
-//                 .sudt_amount_dec(0, 0)          // 0 sudt
-//                 .order_amount_dec(80, 8)        // 80 sudt
-//                 .price(5, 0)                    // 5
-//                 .order_type(OrderType::SellCKB)
-//                 .build(),
-//         );
-//
-//         // Sold 400 ckb, got 79.76 sudt, remain 0.24 sudt.
-//         // Error: we can still sell 1_00_000_000 shannons to buy  20_000_000 sudt.
-//         let output = OrderOutput::new_sudt(SudtCell::new_with_dec(155, 8, 79_76, 6));
-//
-//         let (mut context, tx) = build_test_context(vec![input], vec![output]);
-//         let tx = context.complete_tx(tx);
-//
-//         let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
-//         assert_error_eq!(err, tx_error(ERR_ORDER_STILL_MATCHABLE, 0));
-//
-//         (context, tx)
-//     }
-// );
-
-// test_contract!(test_err_buy_ckb_order_still_matchable, {
-//     let input = OrderInput::new_order(
-//         OrderCell::builder()
-//             .capacity_dec(200, 8)           // 200 ckb
-//             .sudt_amount_dec(200, 8)        // 200 sudt
-//             .order_amount_dec(100, 8)       // 100 ckb
-//             .price(5, -1)                   // 0.5
-//             .order_type(OrderType::BuyCKB)
-//             .build(),
-//     );
-//
-//     // Error: paid 100 sudt, bought 49.85 ckb, we can paid 100 sudt to bought 49.85 ckb
-//     let output = OrderOutput::new_sudt(SudtCell::new_with_dec(249_85, 6, 100, 8));
-//
-//     let (mut context, tx) = build_test_context(vec![input], vec![output]);
-//     let tx = context.complete_tx(tx);
-//
-//     let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
-//     assert_error_eq!(err, tx_error(ERR_ORDER_STILL_MATCHABLE, 0));
-//
-//     (context, tx)
-// });
+test_contract!(
+    test_err_sell_ckb_order_still_matchable_price_exponent_is_negative,
+    {
+        let input = OrderInput::new_order(
+            OrderCell::builder()
+                .capacity_dec(555, 8)           // 555 ckb
+                .sudt_amount_dec(0, 0)          // 0 sudt
+                .order_amount_dec(80, 8)        // 80 sudt
+                .price(51, -1)                  // 5.1
+                .order_type(OrderType::SellCKB)
+                .build(),
+        );
+
+        // Sold 400 ckb, got 79.76 sudt, remain 0.24 sudt.
+        // Error: we can still sell 1_00_000_000 shannons to buy 19_549_019 sudt.
+        let output = OrderOutput::new_sudt(SudtCell::new_with_dec(155, 8, 79_76, 6));
+
+        let (mut context, tx) = build_test_context(vec![input], vec![output]);
+        let tx = context.complete_tx(tx);
+
+        let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+        assert_error_eq!(err, tx_error(ERR_ORDER_STILL_MATCHABLE, 0));
+
+        (context, tx)
+    }
+);
+
+test_contract!(
+    test_err_sell_ckb_order_still_matchable_price_exponent_is_positive,
+    {
+        let input = OrderInput::new_order(
+            OrderCell::builder()
+                .capacity_dec(555, 8)           // 555 ckb
+                .sudt_amount_dec(0, 0)          // 0 sudt
+                .order_amount_dec(8, 8)         // 8 sudt
+                .price(5, 1)                    // 50
+                .order_type(OrderType::SellCKB)
+                .build(),
+        );
+
+        // Sold 400 ckb, got 7.976 sudt, remain 0.024 sudt.
+        // Error: we can still sell 1_00_000_000 shannons to buy  2_000_000 sudt.
+        let output = OrderOutput::new_sudt(SudtCell::new_with_dec(155, 8, 7_976, 5));
+
+        let (mut context, tx) = build_test_context(vec![input], vec![output]);
+        let tx = context.complete_tx(tx);
+
+        let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+        assert_error_eq!(err, tx_error(ERR_ORDER_STILL_MATCHABLE, 0));
+
+        (context, tx)
+    }
+);
+
+test_contract!(test_err_buy_ckb_order_still_matchable, {
+    let input = OrderInput::new_order(
+        OrderCell::builder()
+            .capacity_dec(200, 8)           // 200 ckb
+            .sudt_amount_dec(200, 8)        // 200 sudt
+            .order_amount_dec(100, 8)       // 100 ckb
+            .price(5, -1)                   // 0.5
+            .order_type(OrderType::BuyCKB)
+            .build(),
+    );
+
+    // Error: paid 100 sudt, bought 49.85 ckb, we can paid 100 sudt to bought 49.85 ckb
+    let output = OrderOutput::new_sudt(SudtCell::new_with_dec(249_85, 6, 100, 8));
+
+    let (mut context, tx) = build_test_context(vec![input], vec![output]);
+    let tx = context.complete_tx(tx);
+
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_ORDER_STILL_MATCHABLE, 0));
+
+    (context, tx)
+});
+
+test_contract!(test_err_sell_ckb_order_overfilled, {
+    let input = OrderInput::new_order(
+        OrderCell::builder()
+            .capacity_dec(2000, 8)          // 2000 ckb
+            .sudt_amount(0)                 // 0 sudt
+            .order_amount_dec(100, 8)       // 100 sudt
+            .price(5, 0)                    // 5
+            .order_type(OrderType::SellCKB)
+            .build(),
+    );
+
+    // Error: sold 600 ckb, got 120 sudt, but order amount is 100 sudt
+    let output = OrderOutput::new_sudt(SudtCell::new_with_dec(1400, 8, 120, 8));
+
+    let (mut context, tx) = build_test_context(vec![input], vec![output]);
+    let tx = context.complete_tx(tx);
+
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_ORDER_OVERFILLED, 0));
+
+    (context, tx)
+});
+
+test_contract!(test_err_buy_ckb_order_overfilled, {
+    let input = OrderInput::new_order(
+        OrderCell::builder()
+            .capacity_dec(800, 8)           // 800 ckb
+            .sudt_amount_dec(500, 8)        // 500 sudt
+            .order_amount_dec(500, 8)       // 500 ckb
+            .price(5, 0)                    // 5
+            .order_type(OrderType::BuyCKB)
+            .build(),
+    );
+
+    // Error: paid 150.45 sudt, bought 750 ckb, but order amount is 500 ckb
+    let output = OrderOutput::new_sudt(SudtCell::new_with_dec(1550, 8, 349_55, 6));
+
+    let (mut context, tx) = build_test_context(vec![input], vec![output]);
+    let tx = context.complete_tx(tx);
+
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_ORDER_OVERFILLED, 0));
+
+    (context, tx)
+});
+
+test_contract!(test_err_version_2_order_price_mismatch_zero_fee, {
+    let input = OrderInput::new_order(
+        OrderCell::builder()
+            .capacity_dec(2000, 8)          // 2000 ckb
+            .sudt_amount(0)                 // 0 sudt
+            .order_amount_dec(250, 8)       // 250 sudt
+            .price(5, 0)                    // 5
+            .order_type(OrderType::SellCKB)
+            .version(2)
+            .fee_rate(0)                    // 0%
+            .build(),
+    );
+
+    // Error: sold 505 ckb, got 100 sudt, but there's no fee to pay
+    let output = OrderOutput::new_sudt(SudtCell::new_with_dec(1495, 8, 100, 8));
+
+    let (mut context, tx) = build_test_context(vec![input], vec![output]);
+    let tx = context.complete_tx(tx);
+
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_PRICE_MISMATCH, 0));
+
+    (context, tx)
+});
+
+test_contract!(test_err_version_2_order_fee_rate_too_high, {
+    let input = OrderInput::new_order(
+        OrderCell::builder()
+            .capacity_dec(2000, 8)
+            .sudt_amount(0)
+            .order_amount_dec(250, 8)
+            .price(5, 0)
+            .order_type(OrderType::SellCKB)
+            .version(2)
+            .fee_rate(10000)                // Error: 100% fee rate
+            .build(),
+    );
+
+    let output = OrderOutput::new_sudt(SudtCell::new_with_dec(1500, 8, 100, 8));
+
+    let (mut context, tx) = build_test_context(vec![input], vec![output]);
+    let tx = context.complete_tx(tx);
+
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_ORDER_FEE_RATE_TOO_HIGH, 0));
+
+    (context, tx)
+});
+
+test_contract!(test_err_partial_filled_version_2_order_fee_rate_changed, {
+    let input = OrderInput::new_order(
+        OrderCell::builder()
+            .capacity_dec(2000, 8)
+            .sudt_amount(0)
+            .order_amount_dec(250, 8)
+            .price(5, 0)
+            .order_type(OrderType::SellCKB)
+            .version(2)
+            .fee_rate(100)
+            .build(),
+    );
+
+    let output = OrderOutput::new_order(
+        OrderCell::builder()
+            .capacity_dec(1495, 8)
+            .sudt_amount_dec(100, 8)
+            .order_amount_dec(150, 8)
+            .price(5, 0)
+            .order_type(OrderType::SellCKB)
+            .version(2)
+            .fee_rate(30)                   // Error: fee rate changed
+            .build(),
+    );
+
+    let (mut context, tx) = build_test_context(vec![input], vec![output]);
+    let tx = context.complete_tx(tx);
+
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_OUTPUT_ORDER_FEE_RATE_CHANGED, 0));
+
+    (context, tx)
+});
+
+test_contract!(test_err_sell_ckb_dex_fee_not_paid, {
+    let input = OrderInput::new_order(
+        OrderCell::builder()
+            .capacity_dec(2000, 8)          // 2000 ckb
+            .sudt_amount(0)                 // 0 sudt
+            .order_amount_dec(250, 8)       // 250 sudt
+            .price(5, 0)                    // 5
+            .order_type(OrderType::SellCKB)
+            .build(),
+    );
+
+    let output = OrderOutput::new_order(
+        OrderCell::builder()
+            .capacity_dec(1500, 8)
+            .sudt_amount_dec(100, 8)
+            .order_amount_dec(150, 8)
+            .price(5, 0)
+            .order_type(OrderType::SellCKB)
+            .build(),
+    );
+    // Error: dex fee is 500 * 0.003 ckb, 1 shannon less
+    let fee = OrderOutput::new_free_fee(FreeCell::new(1_49_999_999));
+
+    let (mut context, tx) = build_test_context(vec![input], vec![output, fee]);
+    let tx = context.complete_tx(tx);
+
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_DEX_FEE_NOT_PAID, 0));
+
+    (context, tx)
+});
+
+// Orders of different users are in different lock groups, dex fee is the total of both
+fn build_two_users_sell_ckb_orders() -> (Vec<OrderInput>, Vec<OrderOutput>) {
+    let build_order = |capacity: u64, sudt_amount: u128, order_amount: u128| {
+        OrderCell::builder()
+            .capacity_dec(capacity, 8)
+            .sudt_amount_dec(sudt_amount, 8)
+            .order_amount_dec(order_amount, 8)
+            .price(5, 0)
+            .order_type(OrderType::SellCKB)
+            .build()
+    };
+
+    let inputs = vec![
+        OrderInput::new_order(build_order(2000, 0, 250)),
+        OrderInput::new_order(build_order(2000, 0, 250)),
+    ];
+    let outputs = vec![
+        OrderOutput::new_order(build_order(1500, 100, 150)),
+        OrderOutput::new_order(build_order(1500, 100, 150)),
+    ];
+
+    (inputs, outputs)
+}
+
+test_contract!(test_sell_ckb_orders_of_two_users_pay_total_dex_fee, {
+    let (inputs, mut outputs) = build_two_users_sell_ckb_orders();
+    // Dex fee is 2 * 500 * 0.003 ckb
+    outputs.push(OrderOutput::new_free_fee(FreeCell::new_with_dec(3, 8)));
+
+    let (mut context, tx) = build_test_context(inputs, outputs);
+    let tx = context.complete_tx(tx);
+
+    context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+
+    (context, tx)
+});
+
+test_contract!(test_err_sell_ckb_orders_of_two_users_pay_single_dex_fee, {
+    let (inputs, mut outputs) = build_two_users_sell_ckb_orders();
+    // Error: only dex fee of one order is paid
+    outputs.push(OrderOutput::new_free_fee(FreeCell::new_with_dec(15, 7)));
+
+    let (mut context, tx) = build_test_context(inputs, outputs);
+    let tx = context.complete_tx(tx);
+
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_DEX_FEE_NOT_PAID, 0));
+
+    (context, tx)
+});
+
+test_contract!(test_err_buy_ckb_dex_fee_not_paid, {
+    let input = OrderInput::new_order(
+        OrderCell::builder()
+            .capacity_dec(154, 8)           // 154 ckb
+            .sudt_amount_dec(100, 8)        // 100 sudt
+            .order_amount_dec(50, 8)        // 50 ckb
+            .price(5, -1)                   // 0.5
+            .order_type(OrderType::BuyCKB)
+            .build(),
+    );
+
+    let output = OrderOutput::new_free(FreeCell::new_with_dec(203_85, 6));
+    // Error: dex fee is paid in ckb instead of sudt
+    let fee = OrderOutput::new_free_fee(FreeCell::new_with_dec(1, 8));
+
+    let (mut context, tx) = build_test_context(vec![input], vec![output, fee]);
+    let tx = context.complete_tx(tx);
+
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_DEX_FEE_NOT_PAID, 0));
+
+    (context, tx)
+});
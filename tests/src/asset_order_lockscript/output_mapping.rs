@@ -0,0 +1,85 @@
+use super::*;
+
+const ERR_WRONG_OUTPUT_INDEX_WITNESS: i8 = 60;
+const ERR_DUPLICATED_ORDER_OUTPUT: i8 = 61;
+
+fn build_sell_ckb_order(capacity: u64, sudt_amount: u128, order_amount: u128) -> OrderCell {
+    OrderCell::builder()
+        .capacity_dec(capacity, 8)
+        .sudt_amount_dec(sudt_amount, 8)
+        .order_amount_dec(order_amount, 8)
+        .price(5, 0)
+        .order_type(OrderType::SellCKB)
+        .build()
+}
+
+test_contract!(test_order_output_located_by_witness, {
+    let input = OrderInput::new_order_with_output_index(build_sell_ckb_order(2000, 0, 250), 1);
+
+    // Dex fee 500 * 0.003 = 1.5 ckb
+    let fee = OrderOutput::new_free_fee(FreeCell::new_with_dec(15, 7));
+    // Sold 500 ckb, got 100 sudt, remain 150 sudt
+    let output = OrderOutput::new_order(build_sell_ckb_order(1500, 100, 150))
+        .custom_lock_args(user_lock_args(0));
+
+    let (mut context, tx) = build_test_context(vec![input], vec![fee, output]);
+    let tx = context.complete_tx(tx);
+
+    context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+
+    (context, tx)
+});
+
+test_contract!(test_err_wrong_output_index_witness, {
+    let input = {
+        // Error: output index should be 4 bytes
+        let witness = WitnessArgs::new_builder()
+            .output_type(Some(Bytes::from(vec![1u8, 0])).pack())
+            .build();
+
+        OrderInput::Order {
+            cell_deps:        None,
+            cell:             build_sell_ckb_order(2000, 0, 250),
+            custom_lock_args: None,
+            witness:          Some(witness.as_bytes()),
+        }
+    };
+
+    let fee = OrderOutput::new_free_fee(FreeCell::new_with_dec(15, 7));
+    let output = OrderOutput::new_order(build_sell_ckb_order(1500, 100, 150))
+        .custom_lock_args(user_lock_args(0));
+
+    let (mut context, tx) = build_test_context(vec![input], vec![fee, output]);
+    let tx = context.complete_tx(tx);
+
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_WRONG_OUTPUT_INDEX_WITNESS, 0));
+
+    (context, tx)
+});
+
+test_contract!(test_err_duplicated_order_output, {
+    let user_lock_hash = always_success_script(user_lock_args(0))
+        .calc_script_hash()
+        .as_bytes();
+
+    // Error: two orders of the same user claim the first output
+    let build_input = || {
+        let cell = build_sell_ckb_order(2000, 0, 250);
+        OrderInput::new_order_with_output_index(cell, 0).custom_lock_args(user_lock_hash.clone())
+    };
+    let (input0, input1) = (build_input(), build_input());
+
+    let output = OrderOutput::new_order(build_sell_ckb_order(1500, 100, 150));
+    let fee = OrderOutput::new_free_fee(FreeCell::new_with_dec(15, 7));
+
+    let (mut context, tx) = build_test_context(vec![input0, input1], vec![output, fee]);
+    let tx = context.complete_tx(tx);
+
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_DUPLICATED_ORDER_OUTPUT, 0));
+
+    (context, tx)
+});
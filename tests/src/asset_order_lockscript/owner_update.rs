@@ -0,0 +1,225 @@
+use super::*;
+use ckb_dyn_lock::test_tool;
+
+const ERR_OUTPUT_ORDER_PRICE_CHANGED: i8 = 12;
+const ERR_REPRICED_MULTIPLE_ORDERS: i8 = 65;
+const ERR_MERGED_ORDER_AMOUNT_MISMATCH: i8 = 66;
+const ERR_REPRICED_ORDER_ASSETS_CHANGED: i8 = 67;
+const ERR_WRONG_OWNER_UPDATE_WITNESS: i8 = 125;
+const ERR_UPDATED_ORDER_LOCK_CHANGED: i8 = 126;
+
+// Operation is put in the first order, updated order is the first output
+fn verify_owner_update(
+    orders: Vec<OrderCell>,
+    operation: Option<u8>,
+    updated: Vec<OrderCell>,
+) -> Result<(), ckb_tool::ckb_error::Error> {
+    let (owner_input, owner_lock_hash) = build_owner_input();
+
+    let mut inputs = vec![owner_input];
+    for (idx, order) in orders.into_iter().enumerate() {
+        let input = OrderInput::new_order(order).custom_lock_args(owner_lock_hash.clone());
+        let input = match operation {
+            Some(operation) if idx == 0 => input.owner_update(operation, &[0]),
+            _ => input,
+        };
+        inputs.push(input);
+    }
+
+    let outputs = updated
+        .into_iter()
+        .map(|order| OrderOutput::new_order(order).custom_lock_args(owner_lock_args()));
+
+    let (mut context, tx) = build_test_context(inputs, outputs.collect());
+    let tx = context.complete_tx(tx);
+
+    context.verify_tx(&tx, MAX_CYCLES).map(|_| ())
+}
+
+#[test]
+fn test_merge_order_cells() {
+    let orders = vec![
+        build_owner_order(1000, 0, 50, 5),
+        build_owner_order(500, 20, 30, 5),
+    ];
+    let merged = vec![build_owner_order(1500, 20, 80, 5)];
+
+    verify_owner_update(orders, Some(OWNER_UPDATE_MERGE), merged).expect("pass verification");
+}
+
+#[test]
+fn test_err_merge_order_cells_with_different_price() {
+    // Error: order prices are different
+    let orders = vec![
+        build_owner_order(1000, 0, 50, 5),
+        build_owner_order(500, 20, 30, 6),
+    ];
+    let merged = vec![build_owner_order(1500, 20, 80, 5)];
+
+    let err = verify_owner_update(orders, Some(OWNER_UPDATE_MERGE), merged).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_OUTPUT_ORDER_PRICE_CHANGED, 1));
+}
+
+#[test]
+fn test_err_merged_order_amount_mismatch() {
+    // Error: merged order amount should be 80
+    let orders = vec![
+        build_owner_order(1000, 0, 50, 5),
+        build_owner_order(500, 20, 30, 5),
+    ];
+    let merged = vec![build_owner_order(1500, 20, 81, 5)];
+
+    let err = verify_owner_update(orders, Some(OWNER_UPDATE_MERGE), merged).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_MERGED_ORDER_AMOUNT_MISMATCH, 1));
+}
+
+#[test]
+fn test_err_wrong_owner_update_witness() {
+    let orders = vec![
+        build_owner_order(1000, 0, 50, 5),
+        build_owner_order(500, 20, 30, 5),
+    ];
+    let merged = vec![build_owner_order(1500, 20, 80, 5)];
+
+    // Error: unknown owner update operation
+    let err = verify_owner_update(orders, Some(9), merged).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_WRONG_OWNER_UPDATE_WITNESS, 1));
+}
+
+#[test]
+fn test_err_merged_order_lock_changed() {
+    let (owner_input, owner_lock_hash) = build_owner_input();
+    let inputs = vec![
+        owner_input,
+        OrderInput::new_order(build_owner_order(1000, 0, 50, 5))
+            .custom_lock_args(owner_lock_hash.clone())
+            .owner_update(OWNER_UPDATE_MERGE, &[0]),
+        OrderInput::new_order(build_owner_order(500, 20, 30, 5)).custom_lock_args(owner_lock_hash),
+    ];
+
+    // Error: merged order is sent to owner lock instead of order lock
+    let merged = OrderOutput::new_sudt(SudtCell::new_with_dec(1500, 8, 20, 8))
+        .custom_lock_args(owner_lock_args());
+
+    let (mut context, tx) = build_test_context(inputs, vec![merged]);
+    let tx = context.complete_tx(tx);
+
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_UPDATED_ORDER_LOCK_CHANGED, 1));
+}
+
+#[test]
+fn test_cancel_and_place_orders_at_same_price() {
+    // Without update operation, orders are cancelled and outputs are new orders
+    let orders = vec![
+        build_owner_order(1000, 0, 50, 5),
+        build_owner_order(500, 20, 30, 5),
+    ];
+    let placed = vec![
+        build_owner_order(1000, 0, 50, 5),
+        build_owner_order(500, 20, 30, 5),
+    ];
+
+    verify_owner_update(orders, None, placed).expect("pass verification");
+}
+
+#[test]
+fn test_cancel_and_place_order_at_new_price() {
+    let orders = vec![build_owner_order(1000, 0, 50, 5)];
+    let placed = vec![build_owner_order(800, 0, 40, 6)];
+
+    verify_owner_update(orders, None, placed).expect("pass verification");
+}
+
+#[test]
+fn test_reprice_order() {
+    let orders = vec![build_owner_order(1000, 0, 50, 5)];
+    let repriced = vec![build_owner_order(1000, 0, 40, 6)];
+
+    verify_owner_update(orders, Some(OWNER_UPDATE_REPRICE), repriced).expect("pass verification");
+}
+
+#[test]
+fn test_err_repriced_order_assets_changed() {
+    // Error: capacity should be kept
+    let orders = vec![build_owner_order(1000, 0, 50, 5)];
+    let repriced = vec![build_owner_order(999, 0, 40, 6)];
+
+    let err = verify_owner_update(orders, Some(OWNER_UPDATE_REPRICE), repriced).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_REPRICED_ORDER_ASSETS_CHANGED, 1));
+}
+
+#[test]
+fn test_err_merge_single_order_with_new_price() {
+    // Error: price can only be changed by re-price operation
+    let orders = vec![build_owner_order(1000, 0, 50, 5)];
+    let merged = vec![build_owner_order(1000, 0, 40, 6)];
+
+    let err = verify_owner_update(orders, Some(OWNER_UPDATE_MERGE), merged).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_OUTPUT_ORDER_PRICE_CHANGED, 1));
+}
+
+#[test]
+fn test_err_reprice_multiple_orders() {
+    // Error: only a single order can be re-priced
+    let orders = vec![
+        build_owner_order(1000, 0, 50, 5),
+        build_owner_order(500, 20, 30, 5),
+    ];
+    let repriced = vec![build_owner_order(1500, 20, 80, 6)];
+
+    let err = verify_owner_update(orders, Some(OWNER_UPDATE_REPRICE), repriced).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_REPRICED_MULTIPLE_ORDERS, 1));
+}
+
+#[test]
+fn test_reprice_order_using_signature_witness() {
+    // generate key pair
+    let privkey = Generator::random_privkey();
+    let pubkey = privkey.pubkey().expect("pubkey");
+    let eth_pubkey = DynLock::eth_pubkey(pubkey);
+
+    let mut context = Context::default();
+
+    // Deploy dependencies
+    let (secp256k1_keccak256_out_point, keccak256_deps) = DynLock::deploy(&mut context);
+    let keccak256_lock_script = context
+        .build_script(&secp256k1_keccak256_out_point, eth_pubkey)
+        .expect("build secp256k1 keccak256 lock script");
+    let keccak256_lock_hash = keccak256_lock_script.calc_script_hash().as_bytes();
+
+    let order_input = {
+        let witness = WitnessArgs::new_builder()
+            .input_type(Some(keccak256_lock_script.as_bytes()).pack())
+            .output_type(Some(owner_update_witness(OWNER_UPDATE_REPRICE, &[0])).pack())
+            .build();
+
+        OrderInput::Order {
+            cell_deps:        Some(keccak256_deps),
+            cell:             build_owner_order(1000, 0, 50, 5),
+            custom_lock_args: Some(keccak256_lock_hash.clone()),
+            witness:          Some(witness.as_bytes()),
+        }
+    };
+
+    let output = OrderOutput::new_order(build_owner_order(1000, 0, 40, 6));
+    let tx = build_tx(&mut context, vec![order_input], vec![output]);
+
+    // Keep order lock args
+    let tx = {
+        let output = tx.outputs().get(0).expect("repriced order");
+        let order_lock = output
+            .lock()
+            .as_builder()
+            .args(keccak256_lock_hash.pack())
+            .build();
+        let output = output.as_builder().lock(order_lock).build();
+        tx.as_advanced_builder().set_outputs(vec![output]).build()
+    };
+    let tx = context.complete_tx(tx);
+
+    let tx = test_tool::secp256k1_keccak256::sign_tx(tx, &privkey);
+    context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+}
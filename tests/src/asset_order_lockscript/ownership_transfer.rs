@@ -0,0 +1,218 @@
+use super::*;
+
+const ERR_TRANSFERRED_ORDER_COUNT_MISMATCH: i8 = 116;
+const ERR_WRONG_TRANSFERRED_USER_LOCK_HASH_SIZE: i8 = 117;
+const ERR_TRANSFERRED_ORDER_CHANGED: i8 = 118;
+const ERR_WRONG_OWNER_UPDATE_WITNESS: i8 = 125;
+
+const NEW_OWNER_LOCK_ARGS: &[u8] = b"new owner";
+
+// Transfer operation is put in the first order, followed by output indexes of all orders
+fn build_transfer_tx(
+    orders: Vec<OrderCell>,
+    output_indexes: &[u32],
+    outputs: Vec<OrderOutput>,
+) -> (Context, TransactionView) {
+    let (owner_input, owner_lock_hash) = build_owner_input();
+
+    let mut inputs = vec![owner_input];
+    for (idx, order) in orders.into_iter().enumerate() {
+        let input = OrderInput::new_order(order).custom_lock_args(owner_lock_hash.clone());
+        let input = match idx {
+            0 => input.owner_update(OWNER_UPDATE_TRANSFER, output_indexes),
+            _ => input,
+        };
+        inputs.push(input);
+    }
+
+    let (mut context, tx) = build_test_context(inputs, outputs);
+    let tx = context.complete_tx(tx);
+    (context, tx)
+}
+
+fn transferred_order(order: OrderCell) -> OrderOutput {
+    OrderOutput::new_order(order).custom_lock_args(Bytes::from_static(NEW_OWNER_LOCK_ARGS))
+}
+
+test_contract!(test_transfer_order_ownership, {
+    let orders = vec![
+        build_owner_order(1000, 0, 50, 5),
+        build_owner_order(500, 20, 30, 5),
+    ];
+    let outputs = vec![
+        transferred_order(build_owner_order(1000, 0, 50, 5)),
+        transferred_order(build_owner_order(500, 20, 30, 5)),
+    ];
+
+    let (mut context, tx) = build_transfer_tx(orders, &[0, 1], outputs);
+    context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+
+    (context, tx)
+});
+
+test_contract!(test_transfer_orders_to_reordered_outputs, {
+    let orders = vec![
+        build_owner_order(1000, 0, 50, 5),
+        build_owner_order(500, 20, 30, 5),
+    ];
+    // Orders are paired with outputs by output indexes in witness
+    let outputs = vec![
+        transferred_order(build_owner_order(500, 20, 30, 5)),
+        transferred_order(build_owner_order(1000, 0, 50, 5)),
+    ];
+
+    let (mut context, tx) = build_transfer_tx(orders, &[1, 0], outputs);
+    context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+
+    (context, tx)
+});
+
+test_contract!(test_transfer_and_place_new_order, {
+    let orders = vec![build_owner_order(1000, 0, 50, 5)];
+    // Owner input capacity is placed as a new order, which isn't transferred
+    let outputs = vec![
+        transferred_order(build_owner_order(1000, 0, 50, 5)),
+        OrderOutput::new_order(build_owner_order(100, 0, 10, 5))
+            .custom_lock_args(owner_lock_args()),
+    ];
+
+    let (mut context, tx) = build_transfer_tx(orders, &[0], outputs);
+    context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+
+    (context, tx)
+});
+
+test_contract!(test_transfer_orders_of_two_owners, {
+    let (owner_input, owner_lock_hash) = build_owner_input();
+    let other_owner_lock_args = user_lock_args(101);
+    let other_owner_lock_hash =
+        always_success_script(other_owner_lock_args.clone()).calc_script_hash();
+    let other_owner_input =
+        build_unlock_input(other_owner_lock_args, WitnessArgs::default().as_bytes());
+
+    let order_input = OrderInput::new_order(build_owner_order(1000, 0, 50, 5))
+        .custom_lock_args(owner_lock_hash)
+        .owner_update(OWNER_UPDATE_TRANSFER, &[0]);
+    let other_order_input = OrderInput::new_order(build_owner_order(500, 20, 30, 5))
+        .custom_lock_args(other_owner_lock_hash.as_bytes())
+        .owner_update(OWNER_UPDATE_TRANSFER, &[1]);
+
+    let inputs = vec![
+        owner_input,
+        other_owner_input,
+        order_input,
+        other_order_input,
+    ];
+    let outputs = vec![
+        transferred_order(build_owner_order(1000, 0, 50, 5)),
+        transferred_order(build_owner_order(500, 20, 30, 5)),
+    ];
+
+    let (mut context, tx) = build_test_context(inputs, outputs);
+    let tx = context.complete_tx(tx);
+
+    context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+
+    (context, tx)
+});
+
+test_contract!(test_cancel_order_with_other_user_order_output, {
+    let (owner_input, owner_lock_hash) = build_owner_input();
+    let order_input =
+        OrderInput::new_order(build_owner_order(1000, 20, 50, 5)).custom_lock_args(owner_lock_hash);
+
+    // Without transfer operation, other user's order output is just a new order
+    let refund = OrderOutput::new_sudt(SudtCell::new_with_dec(1000, 8, 20, 8))
+        .custom_lock_args(owner_lock_args());
+    let other_order = transferred_order(build_owner_order(100, 0, 10, 5));
+
+    let (mut context, tx) =
+        build_test_context(vec![owner_input, order_input], vec![refund, other_order]);
+    let tx = context.complete_tx(tx);
+
+    context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+
+    (context, tx)
+});
+
+test_contract!(test_err_transferred_order_changed, {
+    let orders = vec![build_owner_order(1000, 0, 50, 5)];
+    // Error: order amount is changed
+    let outputs = vec![transferred_order(build_owner_order(1000, 0, 60, 5))];
+
+    let (mut context, tx) = build_transfer_tx(orders, &[0], outputs);
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_TRANSFERRED_ORDER_CHANGED, 1));
+
+    (context, tx)
+});
+
+test_contract!(test_err_transferred_order_not_locked_by_order_lock, {
+    let orders = vec![build_owner_order(1000, 20, 50, 5)];
+    // Error: transferred output is a plain sudt cell
+    let outputs = vec![OrderOutput::new_sudt(SudtCell::new_with_dec(
+        1000, 8, 20, 8,
+    ))];
+
+    let (mut context, tx) = build_transfer_tx(orders, &[0], outputs);
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_TRANSFERRED_ORDER_CHANGED, 1));
+
+    (context, tx)
+});
+
+test_contract!(test_err_wrong_transferred_user_lock_hash_size, {
+    let orders = vec![build_owner_order(1000, 0, 50, 5)];
+    // Error: new order lock args isn't a lock hash
+    let outputs = vec![OrderOutput::new_order(build_owner_order(1000, 0, 50, 5))
+        .custom_order_lock_args(Bytes::from_static(NEW_OWNER_LOCK_ARGS))];
+
+    let (mut context, tx) = build_transfer_tx(orders, &[0], outputs);
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_WRONG_TRANSFERRED_USER_LOCK_HASH_SIZE, 1));
+
+    (context, tx)
+});
+
+test_contract!(test_err_transferred_order_count_mismatch, {
+    let orders = vec![
+        build_owner_order(1000, 0, 50, 5),
+        build_owner_order(500, 20, 30, 5),
+    ];
+    // Error: every order in group must be transferred
+    let outputs = vec![transferred_order(build_owner_order(1000, 0, 50, 5))];
+
+    let (mut context, tx) = build_transfer_tx(orders, &[0], outputs);
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_TRANSFERRED_ORDER_COUNT_MISMATCH, 1));
+
+    (context, tx)
+});
+
+test_contract!(test_err_transferred_orders_share_output, {
+    let orders = vec![
+        build_owner_order(1000, 0, 50, 5),
+        build_owner_order(1000, 0, 50, 5),
+    ];
+    // Error: both orders are transferred to the first output
+    let outputs = vec![
+        transferred_order(build_owner_order(1000, 0, 50, 5)),
+        transferred_order(build_owner_order(1000, 0, 50, 5)),
+    ];
+
+    let (mut context, tx) = build_transfer_tx(orders, &[0, 0], outputs);
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_WRONG_OWNER_UPDATE_WITNESS, 1));
+
+    (context, tx)
+});
@@ -0,0 +1,349 @@
+use super::*;
+
+use num_bigint::BigUint;
+
+#[path = "../../../contracts/asset-order-lockscript/src/constants.rs"]
+mod constants;
+#[path = "../../../contracts/asset-order-lockscript/src/u256.rs"]
+mod u256;
+
+use constants::{FEE_DECIMAL, OVERFILL_TOLERANCE, OVERFILL_TOLERANCE_DECIMAL};
+use u256::{Product, U256};
+
+const DIFF_ROUNDS: usize = 20_000;
+
+// Deterministic xorshift, so failed cases can be reproduced
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn next_u128(&mut self) -> u128 {
+        (u128::from(self.next_u64()) << 64) | u128::from(self.next_u64())
+    }
+
+    // Random bit length, so both small and huge amounts are covered
+    fn amount(&mut self) -> u128 {
+        let bits = self.next_u64() % 129;
+        match bits {
+            0 => 0,
+            128 => self.next_u128(),
+            _ => self.next_u128() & ((1u128 << bits) - 1),
+        }
+    }
+
+    fn ckb(&mut self) -> u128 {
+        self.amount() & u128::from(u64::MAX)
+    }
+
+    fn effect(&mut self) -> u64 {
+        let effect = self.ckb() as u64;
+        if effect == 0 {
+            1
+        } else {
+            effect
+        }
+    }
+
+    fn exponent(&mut self) -> i8 {
+        self.next_u64() as i8
+    }
+
+    fn fee(&mut self) -> u128 {
+        u128::from(self.next_u64()) % FEE_DECIMAL
+    }
+}
+
+// One side of price inequality: product of factors, optionally multiplied by 10^exp
+struct Side<'a> {
+    factors: &'a [u128],
+    exp:     u32,
+}
+
+impl<'a> Side<'a> {
+    fn new(factors: &'a [u128]) -> Self {
+        Side { factors, exp: 0 }
+    }
+
+    fn pow10(factors: &'a [u128], exp: u32) -> Self {
+        Side { factors, exp }
+    }
+
+    fn product(&self) -> Product {
+        let product = self.factors[1..]
+            .iter()
+            .fold(Product::new(self.factors[0]), |acc, f| acc.mul(*f));
+        product.mul_pow10(self.exp)
+    }
+
+    fn biguint(&self) -> BigUint {
+        let product = self.factors[1..]
+            .iter()
+            .fold(BigUint::from(self.factors[0]), |acc, f| acc * *f);
+        product * BigUint::from(10u8).pow(self.exp)
+    }
+}
+
+fn assert_same_ordering(lhs: Side, rhs: Side) {
+    let expected = lhs.biguint().cmp(&rhs.biguint());
+    let got = lhs.product().cmp(&rhs.product());
+
+    assert_eq!(
+        got, expected,
+        "lhs {:?} * 10^{}, rhs {:?} * 10^{}",
+        lhs.factors, lhs.exp, rhs.factors, rhs.exp
+    );
+}
+
+fn abs_exponent(exponent: i8) -> u32 {
+    let opt_abs = exponent.checked_abs();
+    opt_abs.map_or_else(|| 128u32, |e| e as u32)
+}
+
+// Same inequality shapes as order validator
+fn assert_price_checks(fee: u128, effect: u64, exponent: i8, sudt: u128, ckb: u128) {
+    let effect = u128::from(effect);
+    let exp = abs_exponent(exponent);
+    let (d, d_fee) = (FEE_DECIMAL, FEE_DECIMAL - fee);
+
+    if exponent < 0 {
+        // sell_price_matched
+        assert_same_ordering(
+            Side::pow10(&[d_fee, ckb], exp),
+            Side::new(&[d, sudt, effect]),
+        );
+        assert_same_ordering(
+            Side::pow10(&[d_fee, sudt], exp),
+            Side::new(&[d, ckb, effect]),
+        );
+        // sell_ckb_claimable
+        assert_same_ordering(Side::pow10(&[d_fee, ckb], exp), Side::new(&[d, effect]));
+        // buy_ckb_claimable
+        assert_same_ordering(Side::new(&[d_fee, sudt, effect]), Side::pow10(&[d], exp));
+        // validate_buy_ckb_price
+        assert_same_ordering(
+            Side::pow10(&[d, ckb], exp),
+            Side::new(&[d_fee, sudt, effect]),
+        );
+    } else {
+        // sell_price_matched
+        assert_same_ordering(
+            Side::new(&[d_fee, ckb]),
+            Side::pow10(&[d, sudt, effect], exp),
+        );
+        assert_same_ordering(
+            Side::new(&[d_fee, sudt]),
+            Side::pow10(&[d, ckb, effect], exp),
+        );
+        // sell_ckb_claimable
+        assert_same_ordering(Side::new(&[d_fee, ckb]), Side::pow10(&[d, effect], exp));
+        // buy_ckb_claimable
+        assert_same_ordering(Side::pow10(&[d_fee, sudt, effect], exp), Side::new(&[d]));
+        // validate_buy_ckb_price
+        assert_same_ordering(
+            Side::new(&[d, ckb]),
+            Side::pow10(&[d_fee, sudt, effect], exp),
+        );
+    }
+
+    // remained_order_amount
+    assert_same_ordering(
+        Side::new(&[sudt, OVERFILL_TOLERANCE_DECIMAL]),
+        Side::new(&[ckb, OVERFILL_TOLERANCE]),
+    );
+}
+
+#[test]
+fn test_u256_mul_matches_biguint() {
+    let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+
+    for _ in 0..DIFF_ROUNDS {
+        let (a, b, c) = (rng.amount(), rng.amount(), rng.amount());
+        let expected = BigUint::from(a) * b * c;
+
+        let product = U256::from(a)
+            .checked_mul(U256::from(b))
+            .and_then(|p| p.checked_mul(U256::from(c)));
+        assert_eq!(
+            product.is_some(),
+            expected.bits() <= 256,
+            "{} * {} * {}",
+            a,
+            b,
+            c
+        );
+        if let Some(p) = product {
+            assert_eq!(Product::Value(p), Side::new(&[a, b, c]).product());
+        }
+    }
+}
+
+#[test]
+fn test_u256_pow10_boundary() {
+    // 10^77 < 2^256 < 10^78
+    assert!(U256::checked_pow10(77).is_some());
+    assert!(U256::checked_pow10(78).is_none());
+
+    assert_eq!(
+        Product::new(0u64).mul_pow10(128),
+        Product::Value(U256::ZERO)
+    );
+    assert_eq!(Product::new(1u64).mul_pow10(128), Product::Overflow);
+    assert_eq!(
+        Product::new(u128::MAX).mul(0u64),
+        Product::Value(U256::ZERO)
+    );
+}
+
+#[test]
+fn test_price_math_differential_edge_cases() {
+    let fees = [0, 30, FEE_DECIMAL - 1];
+    let effects = [1, 5, u64::MAX];
+    let exponents = [i8::MIN, -127, -77, -60, -1, 0, 1, 60, 77, i8::MAX];
+    let amounts = [0, 1, u128::from(u64::MAX), u128::MAX];
+
+    for fee in fees.iter() {
+        for effect in effects.iter() {
+            for exponent in exponents.iter() {
+                for sudt in amounts.iter() {
+                    for ckb in amounts.iter() {
+                        let ckb = ckb & u128::from(u64::MAX);
+                        assert_price_checks(*fee, *effect, *exponent, *sudt, ckb);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_price_math_differential_random() {
+    let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+
+    for _ in 0..DIFF_ROUNDS {
+        let (fee, effect, exponent) = (rng.fee(), rng.effect(), rng.exponent());
+        let (sudt, ckb) = (rng.amount(), rng.ckb());
+        assert_price_checks(fee, effect, exponent, sudt, ckb);
+    }
+}
+
+#[test]
+fn test_price_math_ordering_near_threshold() {
+    let mut rng = Rng(0xdead_beef_cafe_f00d);
+
+    // Pick ckb close to sudt * price, where accept/reject flips
+    for _ in 0..DIFF_ROUNDS {
+        let (fee, effect) = (rng.fee(), rng.effect());
+        let exponent = (rng.next_u64() % 21) as i8 - 10;
+        let sudt = rng.amount() >> 64;
+
+        let price = BigUint::from(sudt) * effect;
+        let price = if exponent < 0 {
+            price / BigUint::from(10u8).pow(abs_exponent(exponent))
+        } else {
+            price * BigUint::from(10u8).pow(abs_exponent(exponent))
+        };
+        let digits = price.to_u64_digits();
+        let ckb = match digits.len() {
+            0 => 0,
+            1 => u128::from(digits[0]),
+            _ => u128::from(u64::MAX),
+        };
+        let jitter = u128::from(rng.next_u64() % 3);
+        let ckb = (ckb + jitter).saturating_sub(1) & u128::from(u64::MAX);
+
+        assert_price_checks(fee, effect, exponent, sudt, ckb);
+    }
+}
+
+fn build_matched_pairs(binary_name: &str, pairs: usize) -> (Context, TransactionView) {
+    let mut inputs = vec![];
+    let mut outputs = vec![];
+
+    for _ in 0..pairs {
+        inputs.push(OrderInput::new_order(
+            OrderCell::builder()
+                .capacity_dec(2000, 8)          // 2000 ckb
+                .sudt_amount_dec(50, 8)         // 50 sudt
+                .order_amount_dec(150, 8)       // 150 sudt
+                .price(52, -1)                  // 5.2
+                .order_type(OrderType::SellCKB)
+                .build(),
+        ));
+        inputs.push(OrderInput::new_order(
+            OrderCell::builder()
+                .capacity_dec(800, 8)           // 800 ckb
+                .sudt_amount_dec(500, 8)        // 500 sudt
+                .order_amount_dec(750, 8)       // 750 ckb
+                .price(5, 0)                    // 5
+                .order_type(OrderType::BuyCKB)
+                .build(),
+        ));
+
+        outputs.push(OrderOutput::new_sudt(SudtCell::new_with_dec(
+            1247_75, 6, 200, 8,
+        )));
+        outputs.push(OrderOutput::new_sudt(SudtCell::new_with_dec(
+            1550, 8, 349_55, 6,
+        )));
+    }
+
+    // Dex fee 2.25675 ckb and 0.45135 sudt per pair
+    let fee = SudtCell::new_with_dec(2_25675 * pairs as u64, 3, 45135 * pairs as u128, 3);
+    outputs.push(OrderOutput::new_sudt_fee(fee));
+
+    let mut context = Context::default();
+    let tx = build_tx_with_binary(&mut context, binary_name, inputs, outputs);
+    let tx = context.complete_tx(tx);
+    (context, tx)
+}
+
+// Cycles budget of each matched order in a batch. Fixed width price arithmetic takes bounded
+// cycles, so a batch must stay within the budget no matter how many orders are matched.
+const MAX_CYCLES_PER_ORDER: u64 = 2_000_000;
+
+#[test]
+fn test_price_math_batch_cycles() {
+    let mut last_cycles = 0;
+
+    for pairs in [1usize, 4, 16].iter() {
+        let (context, tx) = build_matched_pairs("asset-order-lockscript", *pairs);
+        let cycles = context
+            .verify_tx(&tx, MAX_CYCLES)
+            .expect("pass verification");
+
+        let orders = *pairs as u64 * 2;
+        assert!(
+            cycles <= MAX_CYCLES_PER_ORDER * orders,
+            "{} matched pairs take {} cycles",
+            pairs,
+            cycles
+        );
+        assert!(cycles > last_cycles);
+        last_cycles = cycles;
+
+        // Same batch verified by order lock with replaced big integer price arithmetic, built by
+        // `make biguint-price`
+        let (context, tx) = build_matched_pairs("asset-order-lockscript-biguint-price", *pairs);
+        let biguint_cycles = context
+            .verify_tx(&tx, MAX_CYCLES)
+            .expect("pass verification");
+
+        println!(
+            "{} matched pairs take {} cycles, {} cycles with biguint",
+            pairs, cycles, biguint_cycles
+        );
+        assert!(
+            cycles <= biguint_cycles,
+            "{} matched pairs take {} cycles, more than {} cycles with biguint",
+            pairs,
+            cycles,
+            biguint_cycles
+        );
+    }
+}
@@ -0,0 +1,151 @@
+use super::*;
+
+const ERR_PRICE_MISMATCH: i8 = 23;
+const ERR_PRICE_NOT_NORMALIZED: i8 = 71;
+const ERR_PRICE_DENOMINATOR_IS_ZERO: i8 = 72;
+
+fn build_rational_order(
+    capacity: u64,
+    sudt_amount: u128,
+    order_amount: u128,
+    price: (u64, u64),
+    order_type: OrderType,
+) -> OrderCell {
+    OrderCell::builder()
+        .capacity_dec(capacity, 8)
+        .sudt_amount(sudt_amount)
+        .order_amount_dec(order_amount, 8)
+        .rational_price(price.0, price.1)
+        .order_type(order_type)
+        .version(3)
+        .fee_rate(30)
+        .build()
+}
+
+test_contract!(test_rational_price_sell_ckb_partial_filled, {
+    let input = OrderInput::new_order(build_rational_order(
+        2000,
+        0,
+        500,
+        (10, 3),
+        OrderType::SellCKB,
+    ));
+
+    // Sold 1003 ckb, got 300 sudt at price 10/3, remain 200 sudt
+    let output = OrderOutput::new_order(build_rational_order(
+        997,
+        300_00_000_000,
+        200,
+        (10, 3),
+        OrderType::SellCKB,
+    ));
+    // Dex fee 1003 * 0.003 = 3.009 ckb
+    let fee = OrderOutput::new_free_fee(FreeCell::new_with_dec(3009, 5));
+
+    let (mut context, tx) = build_test_context(vec![input], vec![output, fee]);
+    let tx = context.complete_tx(tx);
+
+    context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+
+    (context, tx)
+});
+
+test_contract!(test_rational_price_buy_ckb_partial_filled, {
+    let input = OrderInput::new_order(build_rational_order(
+        200,
+        500_00_000_000,
+        1500,
+        (10, 3),
+        OrderType::BuyCKB,
+    ));
+
+    // Paid 300.9 sudt, bought 1000 ckb at price 10/3, remain 500 ckb
+    let output = OrderOutput::new_order(build_rational_order(
+        1200,
+        199_10_000_000,
+        500,
+        (10, 3),
+        OrderType::BuyCKB,
+    ));
+    // Dex fee 300.9 * 0.003 = 0.9027 sudt
+    let fee = OrderOutput::new_sudt_fee(SudtCell::new_with_dec(142, 8, 9027, 4));
+
+    let (mut context, tx) = build_test_context(vec![input], vec![output, fee]);
+    let tx = context.complete_tx(tx);
+
+    context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+
+    (context, tx)
+});
+
+test_contract!(test_err_rational_price_mismatch, {
+    let input = OrderInput::new_order(build_rational_order(
+        2000,
+        0,
+        500,
+        (10, 3),
+        OrderType::SellCKB,
+    ));
+
+    // Error: sold 1003 ckb, require at least 300 sudt but got 299 sudt
+    let output = OrderOutput::new_order(build_rational_order(
+        997,
+        299_00_000_000,
+        201,
+        (10, 3),
+        OrderType::SellCKB,
+    ));
+    let fee = OrderOutput::new_free_fee(FreeCell::new_with_dec(3009, 5));
+
+    let (mut context, tx) = build_test_context(vec![input], vec![output, fee]);
+    let tx = context.complete_tx(tx);
+
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_PRICE_MISMATCH, 0));
+
+    (context, tx)
+});
+
+test_contract!(test_err_rational_price_not_normalized, {
+    // Error: should be encoded as 10/3
+    let input = OrderInput::new_order(build_rational_order(
+        2000,
+        0,
+        500,
+        (20, 6),
+        OrderType::SellCKB,
+    ));
+    let output = OrderOutput::new_free(FreeCell::new_with_dec(2000, 8));
+
+    let (mut context, tx) = build_test_context(vec![input], vec![output]);
+    let tx = context.complete_tx(tx);
+
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_PRICE_NOT_NORMALIZED, 0));
+
+    (context, tx)
+});
+
+test_contract!(test_err_rational_price_denominator_is_zero, {
+    // Error: price denominator is zero
+    let input = OrderInput::new_order(build_rational_order(
+        2000,
+        0,
+        500,
+        (10, 0),
+        OrderType::SellCKB,
+    ));
+    let output = OrderOutput::new_free(FreeCell::new_with_dec(2000, 8));
+
+    let (mut context, tx) = build_test_context(vec![input], vec![output]);
+    let tx = context.complete_tx(tx);
+
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_PRICE_DENOMINATOR_IS_ZERO, 0));
+
+    (context, tx)
+});
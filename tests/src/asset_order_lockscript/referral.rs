@@ -0,0 +1,104 @@
+use super::*;
+
+const ERR_REFERRAL_SHARE_OUT_OF_RANGE: i8 = 80;
+const ERR_OUTPUT_ORDER_REFERRAL_CHANGED: i8 = 81;
+const ERR_REFERRAL_FEE_NOT_PAID: i8 = 82;
+
+const REFERRER_LOCK_ARGS: &[u8] = b"referrer";
+
+fn referrer_lock_hash() -> [u8; 32] {
+    let lock_script = always_success_script(Bytes::from_static(REFERRER_LOCK_ARGS));
+    let mut lock_hash = [0u8; 32];
+    lock_hash.copy_from_slice(lock_script.calc_script_hash().as_slice());
+    lock_hash
+}
+
+fn build_referred_order(
+    capacity: u64,
+    sudt_amount: u128,
+    order_amount: u128,
+    share: u16,
+) -> OrderCell {
+    OrderCell::builder()
+        .capacity_dec(capacity, 8)
+        .sudt_amount_dec(sudt_amount, 8)
+        .order_amount_dec(order_amount, 8)
+        .price(5, 0)
+        .order_type(OrderType::SellCKB)
+        .version(2)
+        .fee_rate(30)
+        .referral(referrer_lock_hash(), share)
+        .build()
+}
+
+fn build_referrer_output(capacity: u64, decimal: u32) -> OrderOutput {
+    OrderOutput::new_free(FreeCell::new_with_dec(capacity, decimal))
+        .custom_lock_args(Bytes::from_static(REFERRER_LOCK_ARGS))
+}
+
+test_contract!(test_referral_fee_paid, {
+    let input = OrderInput::new_order(build_referred_order(2000, 0, 250, 5000));
+
+    // Sold 1003 ckb, got 200 sudt, remain 50 sudt
+    let output = OrderOutput::new_order(build_referred_order(997, 200, 50, 5000));
+    // Dex fee 1003 * 0.003 = 3.009 ckb, referrer gets half of it
+    let fee = OrderOutput::new_free_fee(FreeCell::new_with_dec(15045, 4));
+    let referrer = build_referrer_output(15045, 4);
+
+    let (mut context, tx) = build_test_context(vec![input], vec![output, fee, referrer]);
+    let tx = context.complete_tx(tx);
+
+    context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+
+    (context, tx)
+});
+
+test_contract!(test_err_referral_fee_not_paid, {
+    let input = OrderInput::new_order(build_referred_order(2000, 0, 250, 5000));
+
+    let output = OrderOutput::new_order(build_referred_order(997, 200, 50, 5000));
+    // Error: referrer should get 1.5045 ckb
+    let fee = OrderOutput::new_free_fee(FreeCell::new_with_dec(2, 8));
+    let referrer = build_referrer_output(1, 8);
+
+    let (mut context, tx) = build_test_context(vec![input], vec![output, fee, referrer]);
+    let tx = context.complete_tx(tx);
+
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_REFERRAL_FEE_NOT_PAID, 0));
+
+    (context, tx)
+});
+
+test_contract!(test_err_referral_share_out_of_range, {
+    // Error: referrer share should not exceed 100%
+    let input = OrderInput::new_order(build_referred_order(2000, 0, 250, 10001));
+    let output = OrderOutput::new_free(FreeCell::new_with_dec(2000, 8));
+
+    let (mut context, tx) = build_test_context(vec![input], vec![output]);
+    let tx = context.complete_tx(tx);
+
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_REFERRAL_SHARE_OUT_OF_RANGE, 0));
+
+    (context, tx)
+});
+
+test_contract!(test_err_output_order_referral_changed, {
+    let input = OrderInput::new_order(build_referred_order(2000, 0, 250, 5000));
+
+    // Error: referrer share is changed
+    let output = OrderOutput::new_order(build_referred_order(997, 200, 50, 10000));
+    let fee = OrderOutput::new_free_fee(FreeCell::new_with_dec(15045, 4));
+    let referrer = build_referrer_output(15045, 4);
+
+    let (mut context, tx) = build_test_context(vec![input], vec![output, fee, referrer]);
+    let tx = context.complete_tx(tx);
+
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_OUTPUT_ORDER_REFERRAL_CHANGED, 0));
+
+    (context, tx)
+});
@@ -0,0 +1,79 @@
+use super::*;
+
+const ERR_STRICT_CANCELLATION_NOT_REFUNDED: i8 = 110;
+const ERR_OUTPUT_ORDER_STRICT_CANCEL_CHANGED: i8 = 111;
+
+fn build_strict_order(capacity: u64, sudt_amount: u128, order_amount: u128) -> OrderCellBuilder {
+    OrderCell::builder()
+        .capacity_dec(capacity, 8)
+        .sudt_amount_dec(sudt_amount, 8)
+        .order_amount_dec(order_amount, 8)
+        .price(5, 0)
+        .order_type(OrderType::SellCKB)
+        .version(2)
+        .fee_rate(30)
+}
+
+fn build_strict_cancel_tx(refund: SudtCell, refund_to_owner: bool) -> (Context, TransactionView) {
+    let (owner_input, owner_lock_hash) = build_owner_input();
+    let order = build_strict_order(1000, 20, 50).strict_cancel().build();
+    let order_input = OrderInput::new_order(order).custom_lock_args(owner_lock_hash);
+
+    let refund_lock_args = if refund_to_owner {
+        owner_lock_args()
+    } else {
+        Bytes::from_static(b"anywhere")
+    };
+    let output = OrderOutput::new_sudt(refund).custom_lock_args(refund_lock_args);
+
+    let (mut context, tx) = build_test_context(vec![owner_input, order_input], vec![output]);
+    let tx = context.complete_tx(tx);
+    (context, tx)
+}
+
+test_contract!(test_strict_cancel_order_refunded, {
+    // Owner input 100 ckb, refund 999.5 ckb and 20 sudt, pays 0.5 ckb tx fee
+    let (mut context, tx) = build_strict_cancel_tx(SudtCell::new_with_dec(1099_5, 7, 20, 8), true);
+
+    context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+
+    (context, tx)
+});
+
+test_contract!(test_err_strict_cancel_order_not_refunded, {
+    // Error: assets are sent to another lock
+    let (mut context, tx) = build_strict_cancel_tx(SudtCell::new_with_dec(1099_5, 7, 20, 8), false);
+
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_STRICT_CANCELLATION_NOT_REFUNDED, 1));
+
+    (context, tx)
+});
+
+test_contract!(test_err_strict_cancel_order_tx_fee_too_high, {
+    // Error: pays 2 ckb tx fee, at most 1 ckb
+    let (mut context, tx) = build_strict_cancel_tx(SudtCell::new_with_dec(1098, 8, 20, 8), true);
+
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_STRICT_CANCELLATION_NOT_REFUNDED, 1));
+
+    (context, tx)
+});
+
+test_contract!(test_err_output_order_strict_cancel_changed, {
+    let input = OrderInput::new_order(build_strict_order(2000, 0, 250).strict_cancel().build());
+
+    // Error: strict cancel flag is dropped
+    let output = OrderOutput::new_order(build_strict_order(997, 200, 50).build());
+    let fee = OrderOutput::new_free_fee(FreeCell::new_with_dec(3009, 5));
+
+    let (mut context, tx) = build_test_context(vec![input], vec![output, fee]);
+    let tx = context.complete_tx(tx);
+
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_OUTPUT_ORDER_STRICT_CANCEL_CHANGED, 0));
+
+    (context, tx)
+});
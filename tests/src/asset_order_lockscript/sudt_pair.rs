@@ -0,0 +1,282 @@
+use super::*;
+
+const ERR_NEGATIVE_CAPACITY_DIFFERENCE: i8 = 22;
+const ERR_ORDER_STILL_MATCHABLE: i8 = 24;
+const ERR_QUOTE_SUDT_NOT_RECEIVED: i8 = 55;
+const ERR_OUTPUT_NOT_A_QUOTE_SUDT_CELL: i8 = 56;
+const ERR_UNEXPECTED_REFUND_OUTPUT: i8 = 57;
+const ERR_WRONG_REFUND_OUTPUT: i8 = 58;
+
+// Use always success with custom args as quote sudt type script
+const QUOTE_SUDT_ARGS: &[u8] = b"quote sudt";
+
+fn quote_type_hash() -> [u8; 32] {
+    let quote_type_script = always_success_script(Bytes::from_static(QUOTE_SUDT_ARGS));
+
+    let mut type_hash = [0u8; 32];
+    type_hash.copy_from_slice(quote_type_script.calc_script_hash().as_slice());
+    type_hash
+}
+
+// Taker order refunds leftover sudt in refund output, whose index is appended after order role
+fn new_order_with_refund(cell: OrderCell, output_index: u32, refund_index: u32) -> OrderInput {
+    let mut output_witness = output_index.to_le_bytes().to_vec();
+    output_witness.push(0);
+    output_witness.extend_from_slice(&refund_index.to_le_bytes());
+    let witness = WitnessArgs::new_builder()
+        .output_type(Some(Bytes::from(output_witness)).pack())
+        .build();
+
+    OrderInput::Order {
+        cell_deps: None,
+        cell,
+        custom_lock_args: None,
+        witness: Some(witness.as_bytes()),
+    }
+}
+
+fn build_sell_sudt_order(capacity: u64, sudt_amount: u128, order_amount: u128) -> OrderCell {
+    OrderCell::builder()
+        .capacity_dec(capacity, 8)
+        .sudt_amount(sudt_amount)
+        .order_amount_dec(order_amount, 8)
+        .price(2, 0)
+        .order_type(OrderType::SellSudt)
+        .version(2)
+        .fee_rate(30)
+        .quote_type_hash(quote_type_hash())
+        .build()
+}
+
+test_contract!(test_sell_sudt_order_partial_filled, {
+    let input = OrderInput::new_order(build_sell_sudt_order(200, 1000_00_000_000, 500));
+
+    // Sold 200.6 sudt, got 100 quote sudt, remain 400 quote sudt
+    let output = OrderOutput::new_order(build_sell_sudt_order(200, 799_40_000_000, 400));
+    // Dex fee 200.6 * 0.003 = 0.6018 sudt
+    let fee = OrderOutput::new_sudt_fee(SudtCell::new_with_dec(142, 8, 6018, 4));
+    let quote = OrderOutput::new_sudt(SudtCell::new_with_dec(142, 8, 100, 8))
+        .custom_type_args(Bytes::from_static(QUOTE_SUDT_ARGS))
+        .custom_lock_args(user_lock_args(0));
+
+    let (mut context, tx) = build_test_context(vec![input], vec![output, fee, quote]);
+    let tx = context.complete_tx(tx);
+
+    context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+
+    (context, tx)
+});
+
+test_contract!(test_sell_sudt_order_completed, {
+    let input = OrderInput::new_order(build_sell_sudt_order(200, 200_60_000_000, 100));
+
+    // Sold 200.6 sudt, got 100 quote sudt
+    let output = OrderOutput::new_sudt(SudtCell::new_with_dec(200, 8, 100, 8))
+        .custom_type_args(Bytes::from_static(QUOTE_SUDT_ARGS));
+    // Dex fee 200.6 * 0.003 = 0.6018 sudt
+    let fee = OrderOutput::new_sudt_fee(SudtCell::new_with_dec(142, 8, 6018, 4));
+
+    let (mut context, tx) = build_test_context(vec![input], vec![output, fee]);
+    let tx = context.complete_tx(tx);
+
+    context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+
+    (context, tx)
+});
+
+test_contract!(test_err_sell_sudt_quote_not_received, {
+    let input = OrderInput::new_order(build_sell_sudt_order(200, 1000_00_000_000, 500));
+
+    // Error: 100 quote sudt isn't sent to user
+    let output = OrderOutput::new_order(build_sell_sudt_order(200, 799_40_000_000, 400));
+    let fee = OrderOutput::new_sudt_fee(SudtCell::new_with_dec(142, 8, 6018, 4));
+
+    let (mut context, tx) = build_test_context(vec![input], vec![output, fee]);
+    let tx = context.complete_tx(tx);
+
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_QUOTE_SUDT_NOT_RECEIVED, 0));
+
+    (context, tx)
+});
+
+test_contract!(test_err_sell_sudt_output_not_quote, {
+    let input = OrderInput::new_order(build_sell_sudt_order(200, 200_60_000_000, 100));
+
+    // Error: output is a sudt cell of sold sudt
+    let output = OrderOutput::new_sudt(SudtCell::new_with_dec(200, 8, 100, 8));
+    let fee = OrderOutput::new_sudt_fee(SudtCell::new_with_dec(142, 8, 6018, 4));
+
+    let (mut context, tx) = build_test_context(vec![input], vec![output, fee]);
+    let tx = context.complete_tx(tx);
+
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_OUTPUT_NOT_A_QUOTE_SUDT_CELL, 0));
+
+    (context, tx)
+});
+
+test_contract!(test_err_sell_sudt_capacity_decreased, {
+    let input = OrderInput::new_order(build_sell_sudt_order(200, 1000_00_000_000, 500));
+
+    // Error: 1 ckb locked in order cell is taken
+    let output = OrderOutput::new_order(build_sell_sudt_order(199, 799_40_000_000, 400));
+    let fee = OrderOutput::new_sudt_fee(SudtCell::new_with_dec(142, 8, 6018, 4));
+    let quote = OrderOutput::new_sudt(SudtCell::new_with_dec(142, 8, 100, 8))
+        .custom_type_args(Bytes::from_static(QUOTE_SUDT_ARGS))
+        .custom_lock_args(user_lock_args(0));
+
+    let (mut context, tx) = build_test_context(vec![input], vec![output, fee, quote]);
+    let tx = context.complete_tx(tx);
+
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_NEGATIVE_CAPACITY_DIFFERENCE, 0));
+
+    (context, tx)
+});
+
+test_contract!(test_sell_sudt_order_completed_with_refund, {
+    let input = new_order_with_refund(build_sell_sudt_order(200, 300_00_000_000, 100), 0, 2);
+
+    // Sold 200.6 sudt, got 100 quote sudt
+    let output = OrderOutput::new_sudt(SudtCell::new_with_dec(200, 8, 100, 8))
+        .custom_type_args(Bytes::from_static(QUOTE_SUDT_ARGS));
+    // Dex fee 200.6 * 0.003 = 0.6018 sudt
+    let fee = OrderOutput::new_sudt_fee(SudtCell::new_with_dec(142, 8, 6018, 4));
+    // Refund leftover 99.4 sudt
+    let refund = OrderOutput::new_sudt(SudtCell::new_with_dec(142, 8, 994, 7))
+        .custom_lock_args(user_lock_args(0));
+
+    let (mut context, tx) = build_test_context(vec![input], vec![output, fee, refund]);
+    let tx = context.complete_tx(tx);
+
+    context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+
+    (context, tx)
+});
+
+test_contract!(test_err_sell_sudt_refund_still_matchable, {
+    let input = new_order_with_refund(build_sell_sudt_order(200, 300_00_000_000, 150), 0, 2);
+
+    // Error: refunded 99.4 sudt can still buy 49 quote sudt of remained 50
+    let output = OrderOutput::new_sudt(SudtCell::new_with_dec(200, 8, 100, 8))
+        .custom_type_args(Bytes::from_static(QUOTE_SUDT_ARGS));
+    let fee = OrderOutput::new_sudt_fee(SudtCell::new_with_dec(142, 8, 6018, 4));
+    let refund = OrderOutput::new_sudt(SudtCell::new_with_dec(142, 8, 994, 7))
+        .custom_lock_args(user_lock_args(0));
+
+    let (mut context, tx) = build_test_context(vec![input], vec![output, fee, refund]);
+    let tx = context.complete_tx(tx);
+
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_ORDER_STILL_MATCHABLE, 0));
+
+    (context, tx)
+});
+
+test_contract!(test_err_sell_sudt_wrong_refund_output, {
+    let input = new_order_with_refund(build_sell_sudt_order(200, 300_00_000_000, 100), 0, 2);
+
+    let output = OrderOutput::new_sudt(SudtCell::new_with_dec(200, 8, 100, 8))
+        .custom_type_args(Bytes::from_static(QUOTE_SUDT_ARGS));
+    let fee = OrderOutput::new_sudt_fee(SudtCell::new_with_dec(142, 8, 6018, 4));
+    // Error: refund output doesn't belong to user
+    let refund = OrderOutput::new_sudt(SudtCell::new_with_dec(142, 8, 994, 7));
+
+    let (mut context, tx) = build_test_context(vec![input], vec![output, fee, refund]);
+    let tx = context.complete_tx(tx);
+
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_WRONG_REFUND_OUTPUT, 0));
+
+    (context, tx)
+});
+
+test_contract!(test_err_sell_sudt_partial_filled_with_refund, {
+    let input = new_order_with_refund(build_sell_sudt_order(200, 1000_00_000_000, 500), 0, 2);
+
+    // Error: only completed order refunds leftover sudt
+    let output = OrderOutput::new_order(build_sell_sudt_order(200, 799_40_000_000, 400));
+    let fee = OrderOutput::new_sudt_fee(SudtCell::new_with_dec(142, 8, 6018, 4));
+    let quote = OrderOutput::new_sudt(SudtCell::new_with_dec(142, 8, 100, 8))
+        .custom_type_args(Bytes::from_static(QUOTE_SUDT_ARGS))
+        .custom_lock_args(user_lock_args(0));
+
+    let (mut context, tx) = build_test_context(vec![input], vec![output, fee, quote]);
+    let tx = context.complete_tx(tx);
+
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_UNEXPECTED_REFUND_OUTPUT, 0));
+
+    (context, tx)
+});
+
+// Two orders of the same user, one of them has a delegate, so they're in different lock groups
+fn build_same_user_orders() -> (Vec<OrderInput>, Vec<OrderOutput>) {
+    let user_lock_hash = always_success_script(owner_lock_args())
+        .calc_script_hash()
+        .as_bytes();
+    let mut delegate_order_lock_args = user_lock_hash.to_vec();
+    delegate_order_lock_args.extend_from_slice(&[1u8; 32]);
+    let delegate_order_lock_args = Bytes::from(delegate_order_lock_args);
+
+    let inputs = vec![
+        OrderInput::new_order(build_sell_sudt_order(200, 1000_00_000_000, 500))
+            .custom_lock_args(user_lock_hash.clone()),
+        OrderInput::new_order(build_sell_sudt_order(200, 1000_00_000_000, 500))
+            .custom_lock_args(delegate_order_lock_args.clone()),
+    ];
+
+    // Each sold 200.6 sudt, got 100 quote sudt, remain 400 quote sudt
+    let outputs = vec![
+        OrderOutput::new_order(build_sell_sudt_order(200, 799_40_000_000, 400))
+            .custom_order_lock_args(user_lock_hash),
+        OrderOutput::new_order(build_sell_sudt_order(200, 799_40_000_000, 400))
+            .custom_order_lock_args(delegate_order_lock_args),
+        // Dex fee 200.6 * 0.003 * 2 = 1.2036 sudt
+        OrderOutput::new_sudt_fee(SudtCell::new_with_dec(142, 8, 12036, 4)),
+    ];
+
+    (inputs, outputs)
+}
+
+test_contract!(test_sell_sudt_orders_of_same_user_partial_filled, {
+    let (inputs, mut outputs) = build_same_user_orders();
+    // Quote sudt got by both orders
+    let quote = OrderOutput::new_sudt(SudtCell::new_with_dec(142, 8, 200, 8))
+        .custom_type_args(Bytes::from_static(QUOTE_SUDT_ARGS))
+        .custom_lock_args(owner_lock_args());
+    outputs.push(quote);
+
+    let (mut context, tx) = build_test_context(inputs, outputs);
+    let tx = context.complete_tx(tx);
+
+    context
+        .verify_tx(&tx, MAX_CYCLES)
+        .expect("pass verification");
+
+    (context, tx)
+});
+
+test_contract!(test_err_sell_sudt_orders_of_same_user_share_quote, {
+    let (inputs, mut outputs) = build_same_user_orders();
+    // Error: one quote sudt output is counted for both orders
+    let quote = OrderOutput::new_sudt(SudtCell::new_with_dec(142, 8, 100, 8))
+        .custom_type_args(Bytes::from_static(QUOTE_SUDT_ARGS))
+        .custom_lock_args(owner_lock_args());
+    outputs.push(quote);
+
+    let (mut context, tx) = build_test_context(inputs, outputs);
+    let tx = context.complete_tx(tx);
+
+    let err = context.verify_tx(&tx, MAX_CYCLES).unwrap_err();
+    assert_error_eq!(err, tx_error(ERR_QUOTE_SUDT_NOT_RECEIVED, 0));
+
+    (context, tx)
+});
@@ -9,6 +9,8 @@ use ckb_tool::ckb_types::bytes::Bytes;
 mod asset_order_lockscript;
 #[cfg(test)]
 mod liquidity_poll_tests;
+#[cfg(test)]
+mod order_data_tests;
 mod schema;
 
 lazy_static::lazy_static! {
@@ -0,0 +1,256 @@
+use order_data::{Error, OrderData, Price, Referral};
+
+fn v2_order_data() -> OrderData {
+    OrderData {
+        sudt_amount:     50_00_000_000,
+        version:         2,
+        order_amount:    150_00_000_000,
+        price:           Price::Decimal {
+            effect:   52,
+            exponent: -1,
+        },
+        order_type:      0,
+        fee_rate:        Some(30),
+        quote_type_hash: None,
+        expiry:          None,
+        referral:        None,
+        witness_lock:    false,
+        strict_cancel:   false,
+    }
+}
+
+#[test]
+fn test_version_1_roundtrip() {
+    let order = OrderData {
+        version: 1,
+        fee_rate: None,
+        ..v2_order_data()
+    };
+
+    let data = order.encode().unwrap();
+    assert_eq!(data.len(), 43);
+    assert_eq!(OrderData::decode(&data), Ok(order));
+}
+
+#[test]
+fn test_version_2_roundtrip_with_optional_fields() {
+    let order = OrderData {
+        order_type: 2,
+        quote_type_hash: Some([7u8; 32]),
+        expiry: Some(100),
+        ..v2_order_data()
+    };
+
+    let data = order.encode().unwrap();
+    assert_eq!(data.len(), 45 + 32 + 8);
+    assert_eq!(OrderData::decode(&data), Ok(order));
+}
+
+#[test]
+fn test_version_3_roundtrip() {
+    let order = OrderData {
+        version: 3,
+        price: Price::Rational {
+            numerator:   10,
+            denominator: 3,
+        },
+        expiry: Some(100),
+        ..v2_order_data()
+    };
+
+    let data = order.encode().unwrap();
+    assert_eq!(data.len(), 52 + 8);
+    assert_eq!(OrderData::decode(&data), Ok(order));
+}
+
+#[test]
+fn test_referral_roundtrip() {
+    let referral = Some(Referral {
+        lock_hash: [9u8; 32],
+        share:     5000,
+    });
+
+    let order = OrderData {
+        referral,
+        ..v2_order_data()
+    };
+    let data = order.encode().unwrap();
+    assert_eq!(data.len(), 45 + 34);
+    assert_eq!(OrderData::decode(&data), Ok(order));
+
+    let order = OrderData {
+        order_type: 2,
+        quote_type_hash: Some([7u8; 32]),
+        expiry: Some(100),
+        referral,
+        ..v2_order_data()
+    };
+    let data = order.encode().unwrap();
+    assert_eq!(data.len(), 45 + 32 + 8 + 34);
+    assert_eq!(OrderData::decode(&data), Ok(order));
+}
+
+#[test]
+fn test_witness_lock_flag_roundtrip() {
+    let order = OrderData {
+        witness_lock: true,
+        ..v2_order_data()
+    };
+
+    let data = order.encode().unwrap();
+    assert_eq!(data[16], 0x82);
+    assert!(order_data::witness_lock_flag(&data));
+    assert_eq!(OrderData::decode(&data), Ok(order));
+
+    let data = v2_order_data().encode().unwrap();
+    assert!(!order_data::witness_lock_flag(&data));
+}
+
+#[test]
+fn test_strict_cancel_flag_roundtrip() {
+    let order = OrderData {
+        witness_lock: true,
+        strict_cancel: true,
+        ..v2_order_data()
+    };
+
+    let data = order.encode().unwrap();
+    assert_eq!(data[16], 0xc2);
+    assert_eq!(OrderData::decode(&data), Ok(order));
+}
+
+#[test]
+fn test_decode_wrong_data_size() {
+    let data = v2_order_data().encode().unwrap();
+    assert_eq!(OrderData::decode(&data[..44]), Err(Error::WrongDataSize));
+
+    // Sell sudt order requires quote sudt type hash
+    let order = OrderData {
+        order_type: 2,
+        ..v2_order_data()
+    };
+    assert_eq!(
+        OrderData::decode(&order.encode().unwrap()),
+        Err(Error::WrongDataSize)
+    );
+
+    // Expiry must be 8 bytes
+    let mut data = v2_order_data().encode().unwrap();
+    data.extend_from_slice(&[0u8; 4]);
+    assert_eq!(OrderData::decode(&data), Err(Error::WrongDataSize));
+}
+
+#[test]
+fn test_decode_unexpected_version() {
+    let mut data = v2_order_data().encode().unwrap();
+    data[16] = 100;
+    assert_eq!(OrderData::decode(&data), Err(Error::UnexpectedVersion));
+}
+
+#[test]
+fn test_encode_unexpected_version() {
+    let order = OrderData {
+        version: 100,
+        ..v2_order_data()
+    };
+    assert_eq!(order.encode(), Err(Error::UnexpectedVersion));
+}
+
+#[test]
+fn test_roundtrip_every_version_and_flags() {
+    let rational_price = Price::Rational {
+        numerator:   10,
+        denominator: 3,
+    };
+    let orders = vec![
+        OrderData {
+            version: 1,
+            fee_rate: None,
+            ..v2_order_data()
+        },
+        v2_order_data(),
+        OrderData {
+            version: 3,
+            price: rational_price,
+            ..v2_order_data()
+        },
+    ];
+
+    for order in orders {
+        for &(witness_lock, strict_cancel) in &[(false, false), (true, false), (true, true)] {
+            let order = OrderData {
+                witness_lock,
+                strict_cancel,
+                ..order.clone()
+            };
+
+            let data = order.encode().unwrap();
+            let decoded = OrderData::decode(&data).unwrap();
+            assert_eq!(decoded, order);
+            assert_eq!(decoded.encode().unwrap(), data);
+        }
+    }
+}
+
+#[test]
+fn test_encode_version_fields_mismatch() {
+    // Version 1 layout can't carry fee rate or optional fields
+    let order = OrderData {
+        version: 1,
+        ..v2_order_data()
+    };
+    assert_eq!(order.encode(), Err(Error::VersionFieldsMismatch));
+
+    let order = OrderData {
+        version: 1,
+        fee_rate: None,
+        expiry: Some(100),
+        ..v2_order_data()
+    };
+    assert_eq!(order.encode(), Err(Error::VersionFieldsMismatch));
+
+    let order = OrderData {
+        version: 1,
+        fee_rate: None,
+        referral: Some(Referral {
+            lock_hash: [9u8; 32],
+            share:     5000,
+        }),
+        ..v2_order_data()
+    };
+    assert_eq!(order.encode(), Err(Error::VersionFieldsMismatch));
+
+    // Version 2 requires decimal price and fee rate
+    let order = OrderData {
+        fee_rate: None,
+        ..v2_order_data()
+    };
+    assert_eq!(order.encode(), Err(Error::VersionFieldsMismatch));
+
+    let order = OrderData {
+        price: Price::Rational {
+            numerator:   10,
+            denominator: 3,
+        },
+        ..v2_order_data()
+    };
+    assert_eq!(order.encode(), Err(Error::VersionFieldsMismatch));
+
+    // Version 3 requires rational price and fee rate
+    let order = OrderData {
+        version: 3,
+        ..v2_order_data()
+    };
+    assert_eq!(order.encode(), Err(Error::VersionFieldsMismatch));
+
+    let order = OrderData {
+        version: 3,
+        price: Price::Rational {
+            numerator:   10,
+            denominator: 3,
+        },
+        fee_rate: None,
+        ..v2_order_data()
+    };
+    assert_eq!(order.encode(), Err(Error::VersionFieldsMismatch));
+}
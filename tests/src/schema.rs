@@ -1,7 +1,6 @@
 #[allow(dead_code)]
 pub mod generated;
 pub use generated::basic;
-pub use generated::cell_data;
 
 use ckb_tool::ckb_types::{bytes::Bytes, prelude::*};
 
@@ -1,2 +1 @@
 pub mod basic;
-pub mod cell_data;